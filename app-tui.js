@@ -12,8 +12,9 @@ let screen, table, statusBox, filterList;
 
 async function cargarPartidos() {
   if (fs.existsSync(PARTIDOS_FILE)) {
-    const data = fs.readFileSync(PARTIDOS_FILE, 'utf-8');
-    return JSON.parse(data);
+    const data = JSON.parse(fs.readFileSync(PARTIDOS_FILE, 'utf-8'));
+    // Desde la versión 1 los partidos van dentro de un objeto con metadatos
+    return Array.isArray(data) ? data : data.partidos;
   }
   return [];
 }
@@ -0,0 +1,54 @@
+//! Lista los próximos partidos de un equipo.
+//!
+//! ```sh
+//! cargo run -p fecapa --example proximos -- "CP VIC"
+//! ```
+//!
+//! Usa el `partidos.json` de fecapa-explorer y, si no hay, lo pide a las
+//! fuentes de `ajustes.json` sin guardarlo.
+
+use chrono::Local;
+use fecapa::{datos, fuentes};
+
+fn main() {
+    let Some(equipo) = std::env::args().nth(1) else {
+        eprintln!("Uso: proximos <equipo>");
+        std::process::exit(2);
+    };
+
+    let mut partidos = match datos::cargar_partidos() {
+        Ok(archivo) => archivo.partidos,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            std::process::exit(1);
+        }
+    };
+    if partidos.is_empty() {
+        let ajustes = datos::cargar_ajustes().unwrap_or_default();
+        match fuentes::obtener(&ajustes) {
+            Ok(archivo) => partidos = archivo.partidos,
+            Err(e) => {
+                eprintln!("❌ {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let hoy = Local::now().date_naive();
+    let mut proximos: Vec<_> = partidos
+        .iter()
+        .filter(|p| p.juega(&equipo))
+        .filter(|p| p.resultado.is_empty() && p.fecha().is_some_and(|f| f >= hoy))
+        .collect();
+    proximos.sort_by_key(|p| (p.fecha(), p.hora.clone()));
+
+    if proximos.is_empty() {
+        println!("{}: no hay partidos pendientes", equipo);
+    }
+    for p in proximos {
+        println!(
+            "{} {:>5}  {} - {}  ({}, {})",
+            p.data, p.hora, p.local, p.visitante, p.competicion, p.pista
+        );
+    }
+}
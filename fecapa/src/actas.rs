@@ -0,0 +1,212 @@
+use crate::models::{Acta, EventoActa, JugadorActa, Lado, Partido, TipoEvento};
+use crate::pistas::normalizar;
+use scraper::{ElementRef, Html, Selector};
+use std::time::Duration;
+
+fn texto(elemento: ElementRef) -> String {
+    elemento
+        .text()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn celdas(fila: ElementRef) -> Vec<String> {
+    let selector = Selector::parse("td, th").unwrap();
+    fila.select(&selector).map(texto).collect()
+}
+
+fn columna(cabecera: &[String], nombres: &[&str]) -> Option<usize> {
+    cabecera
+        .iter()
+        .position(|c| nombres.iter().any(|n| normalizar(c).starts_with(n)))
+}
+
+fn lado(equipo: &str, p: &Partido) -> Option<Lado> {
+    let equipo = normalizar(equipo);
+    match equipo.as_str() {
+        "L" | "LOCAL" => return Some(Lado::Local),
+        "V" | "VISITANT" | "VISITANTE" => return Some(Lado::Visitante),
+        _ => {}
+    }
+    if !equipo.is_empty() && normalizar(&p.local) == equipo {
+        Some(Lado::Local)
+    } else if !equipo.is_empty() && normalizar(&p.visitante) == equipo {
+        Some(Lado::Visitante)
+    } else {
+        None
+    }
+}
+
+fn tipo_evento(texto: &str) -> Option<TipoEvento> {
+    let texto = normalizar(texto);
+    if texto.contains("BLAVA") || texto.contains("AZUL") {
+        Some(TipoEvento::Azul)
+    } else if texto.contains("VERMELLA") || texto.contains("ROJA") {
+        Some(TipoEvento::Roja)
+    } else if texto.starts_with("GOL") {
+        Some(TipoEvento::Gol)
+    } else {
+        None
+    }
+}
+
+/// Interpreta el HTML de un acta. Las tablas se reconocen por su cabecera
+/// ("Dorsal"/"Jugador" para las alineaciones, "Min."/"Incidència" para los
+/// goles y tarjetas), no por su posición, para tolerar cambios de diseño.
+pub fn parsear_acta(html: &str, url: &str, p: &Partido) -> Acta {
+    let documento = Html::parse_document(html);
+    let tablas = Selector::parse("table").unwrap();
+    let filas = Selector::parse("tr").unwrap();
+    let mut acta = Acta {
+        url: url.to_string(),
+        ..Default::default()
+    };
+    let mut alineaciones = 0;
+
+    for tabla in documento.select(&tablas) {
+        let mut filas_tabla = tabla.select(&filas).map(celdas);
+        let Some(cabecera) = filas_tabla.next() else {
+            continue;
+        };
+
+        let dorsal = columna(&cabecera, &["DORSAL", "NUM"]);
+        let jugador = columna(&cabecera, &["JUGADOR", "NOM"]);
+        let minuto = columna(&cabecera, &["MIN"]);
+        let equipo = columna(&cabecera, &["EQUIP"]);
+        let incidencia = columna(&cabecera, &["INCIDENCIA", "ACCIO", "TIPUS"]);
+
+        match (dorsal, jugador, minuto, equipo, incidencia) {
+            (Some(d), Some(j), None, _, _) => {
+                let jugadores: Vec<JugadorActa> = filas_tabla
+                    .filter(|c| c.len() > d.max(j) && !c[j].is_empty())
+                    .map(|c| JugadorActa {
+                        dorsal: c[d].clone(),
+                        nombre: c[j].clone(),
+                    })
+                    .collect();
+                match alineaciones {
+                    0 => acta.local = jugadores,
+                    1 => acta.visitante = jugadores,
+                    _ => {}
+                }
+                alineaciones += 1;
+            }
+            (_, Some(j), Some(m), Some(e), Some(i)) => {
+                for c in filas_tabla.filter(|c| c.len() > j.max(m).max(e).max(i)) {
+                    if let (Some(lado), Some(tipo)) = (lado(&c[e], p), tipo_evento(&c[i])) {
+                        acta.eventos.push(EventoActa {
+                            minuto: c[m].trim_end_matches('\'').to_string(),
+                            lado,
+                            jugador: c[j].clone(),
+                            tipo,
+                        });
+                    }
+                }
+            }
+            _ => {
+                for c in std::iter::once(cabecera).chain(filas_tabla) {
+                    if c.len() >= 2 && normalizar(&c[0]).starts_with("ARBITRE") && !c[1].is_empty()
+                    {
+                        acta.arbitros.push(c[1].clone());
+                    }
+                }
+            }
+        }
+    }
+    acta
+}
+
+pub fn descargar_acta(p: &Partido) -> Result<Acta, String> {
+    if p.enlace.is_empty() {
+        return Err("Este partido no tiene enlace al acta".to_string());
+    }
+
+    let client = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(15))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let response = client.get(&p.enlace).send().map_err(|e| e.to_string())?;
+
+    if !response.status().is_success() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    let html = response.text().map_err(|e| e.to_string())?;
+    let acta = parsear_acta(&html, &p.enlace, p);
+    if acta.local.is_empty() && acta.eventos.is_empty() {
+        return Err("El acta todavía no está disponible".to_string());
+    }
+    Ok(acta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn partido() -> Partido {
+        Partido {
+            competicion: "BCN BENJAMÍ OR P4".to_string(),
+            local: "CH SANT CELONI".to_string(),
+            visitante: "CP VIC".to_string(),
+            resultado: "3 - 2".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parsea_alineaciones_y_arbitros() {
+        let html = include_str!("../tests/fixtures/acta.html");
+        let acta = parsear_acta(html, "https://example.org/acta", &partido());
+
+        assert_eq!(acta.arbitros, vec!["JOAN GARCIA PUIG", "MARTA SOLER VILA"]);
+        assert_eq!(acta.local.len(), 4);
+        assert_eq!(acta.visitante.len(), 3);
+        assert_eq!(acta.local[1].dorsal, "5");
+        assert_eq!(acta.local[1].nombre, "ARNAU ROCA");
+        assert_eq!(acta.visitante[2].nombre, "MARC COLL");
+    }
+
+    #[test]
+    fn parsea_goles_y_tarjetas() {
+        let html = include_str!("../tests/fixtures/acta.html");
+        let acta = parsear_acta(html, "https://example.org/acta", &partido());
+
+        // El tiempo muerto no es un gol ni una tarjeta.
+        assert_eq!(acta.eventos.len(), 8);
+        assert_eq!(acta.contar(Lado::Local, "ARNAU ROCA", TipoEvento::Gol), 2);
+        assert_eq!(
+            acta.contar(Lado::Visitante, "MARC COLL", TipoEvento::Gol),
+            2
+        );
+        assert_eq!(
+            acta.contar(Lado::Visitante, "BIEL PRAT", TipoEvento::Azul),
+            1
+        );
+        assert_eq!(
+            acta.contar(Lado::Visitante, "BIEL PRAT", TipoEvento::Roja),
+            1
+        );
+        assert_eq!(acta.eventos[0].minuto, "05");
+
+        let goles = |lado| {
+            acta.eventos
+                .iter()
+                .filter(|e| e.lado == lado && e.tipo == TipoEvento::Gol)
+                .count()
+        };
+        assert_eq!((goles(Lado::Local), goles(Lado::Visitante)), (3, 2));
+    }
+
+    #[test]
+    fn acta_sin_publicar_queda_vacia() {
+        let html = include_str!("../tests/fixtures/acta_buida.html");
+        let acta = parsear_acta(html, "https://example.org/acta", &partido());
+
+        assert!(acta.arbitros.is_empty());
+        assert!(acta.local.is_empty() && acta.visitante.is_empty());
+        assert!(acta.eventos.is_empty());
+    }
+}
@@ -0,0 +1,85 @@
+use crate::models::Partido;
+use scraper::{ElementRef, Html, Selector};
+
+/// Página de la agenda de la FECAPA.
+pub const URL_AGENDA: &str = "https://www.hoqueipatins.fecapa.cat/ag/";
+
+fn texto(elemento: ElementRef) -> String {
+    elemento
+        .text()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Lee la tabla de la agenda igual que `scrape-hockey.js`: una fila por
+/// partido con competición, fecha, hora, (escudo), local, (escudo),
+/// visitante, resultado y pista. Los enlaces relativos se resuelven contra
+/// `url`.
+pub fn parsear_agenda(html: &str, url: &str) -> Vec<Partido> {
+    let documento = Html::parse_document(html);
+    let filas = Selector::parse("table tr").unwrap();
+    let celdas = Selector::parse("td").unwrap();
+    let enlaces = Selector::parse("a[href]").unwrap();
+    let base = reqwest::Url::parse(url).ok();
+
+    let mut partidos = Vec::new();
+    for fila in documento.select(&filas) {
+        let columnas: Vec<String> = fila.select(&celdas).map(texto).collect();
+        if columnas.len() < 7 {
+            continue;
+        }
+        let columna = |i: usize| columnas.get(i).cloned().unwrap_or_default();
+        let enlace = fila
+            .select(&enlaces)
+            .next()
+            .and_then(|a| a.value().attr("href"))
+            .map(|href| match base.as_ref().and_then(|b| b.join(href).ok()) {
+                Some(absoluto) => absoluto.to_string(),
+                None => href.to_string(),
+            })
+            .unwrap_or_default();
+
+        let p = Partido {
+            competicion: columna(0),
+            data: columna(1),
+            hora: columna(2),
+            local: columna(4),
+            visitante: columna(6),
+            resultado: columna(7),
+            pista: columna(8),
+            enlace,
+            ..Default::default()
+        };
+        if !p.competicion.is_empty() || !p.local.is_empty() || !p.visitante.is_empty() {
+            partidos.push(p);
+        }
+    }
+    partidos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsea_filas_de_la_agenda() {
+        let html = include_str!("../tests/fixtures/agenda.html");
+        let partidos = parsear_agenda(html, URL_AGENDA);
+
+        assert_eq!(partidos.len(), 2);
+        assert_eq!(partidos[0].competicion, "BCN BENJAMÍ OR P4");
+        assert_eq!(partidos[0].data, "25/10/2026");
+        assert_eq!(partidos[0].hora, "10:00");
+        assert_eq!(partidos[0].local, "CH SANT CELONI");
+        assert_eq!(partidos[0].visitante, "CP VIC");
+        assert_eq!(partidos[0].resultado, "");
+        assert_eq!(partidos[0].pista, "PAV. MUNICIPAL SANT CELONI");
+        assert_eq!(partidos[1].resultado, "3 - 2");
+        assert_eq!(
+            partidos[1].enlace,
+            "https://www.hoqueipatins.fecapa.cat/ag/acta.php?id=123"
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use crate::models::Partido;
+use chrono::{NaiveTime, Utc};
+
+/// Duración que se da a cada partido en el calendario.
+pub const DURACION_MINUTOS: i64 = 90;
+
+/// Escapa un texto para un valor iCalendar (RFC 5545, 3.3.11).
+fn escapar(texto: &str) -> String {
+    texto
+        .trim()
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Parte las líneas de más de 75 bytes; las continuaciones empiezan por un
+/// espacio.
+fn plegar(linea: &str) -> String {
+    let mut plegada = String::new();
+    let mut bytes = 0;
+    for c in linea.chars() {
+        if bytes + c.len_utf8() > 75 {
+            plegada.push_str("\r\n ");
+            bytes = 1;
+        }
+        plegada.push(c);
+        bytes += c.len_utf8();
+    }
+    plegada.push_str("\r\n");
+    plegada
+}
+
+/// Calendario iCalendar con los partidos que tienen fecha, para importarlo
+/// en el móvil o en cualquier aplicación de calendario. Las horas van sin
+/// zona horaria (la hora local de la agenda); los partidos sin hora son de
+/// día entero. El UID sale de la clave del partido y de su fecha, así que al
+/// volver a importar se actualizan en lugar de duplicarse y los cruces
+/// repetidos de un playoff quedan como eventos distintos.
+pub fn a_ics(partidos: &[Partido]) -> String {
+    let sello = Utc::now().format("%Y%m%dT%H%M%SZ");
+    let mut ics = String::new();
+    for linea in [
+        "BEGIN:VCALENDAR",
+        "VERSION:2.0",
+        "PRODID:-//fecapa-explorer//ES",
+        "CALSCALE:GREGORIAN",
+    ] {
+        ics.push_str(&plegar(linea));
+    }
+    for p in partidos {
+        let Some(fecha) = p.fecha() else {
+            continue;
+        };
+        let mut lineas = vec![
+            "BEGIN:VEVENT".to_string(),
+            format!(
+                "UID:{}-{}@fecapa-explorer",
+                escapar(&p.clave_temporada()).replace(' ', "-"),
+                fecha.format("%Y%m%d")
+            ),
+            format!("DTSTAMP:{}", sello),
+        ];
+        match NaiveTime::parse_from_str(p.hora.trim(), "%H:%M") {
+            Ok(hora) => {
+                let inicio = fecha.and_time(hora);
+                let fin = inicio + chrono::Duration::minutes(DURACION_MINUTOS);
+                lineas.push(format!("DTSTART:{}", inicio.format("%Y%m%dT%H%M%S")));
+                lineas.push(format!("DTEND:{}", fin.format("%Y%m%dT%H%M%S")));
+            }
+            Err(_) => {
+                lineas.push(format!("DTSTART;VALUE=DATE:{}", fecha.format("%Y%m%d")));
+                let siguiente = fecha.succ_opt().unwrap_or(fecha);
+                lineas.push(format!("DTEND;VALUE=DATE:{}", siguiente.format("%Y%m%d")));
+            }
+        }
+        lineas.push(format!(
+            "SUMMARY:{}",
+            escapar(&format!("{} - {}", p.local.trim(), p.visitante.trim()))
+        ));
+        if !p.pista.trim().is_empty() {
+            lineas.push(format!("LOCATION:{}", escapar(&p.pista)));
+        }
+        let mut descripcion = p.competicion.trim().to_string();
+        if !p.resultado.trim().is_empty() {
+            descripcion.push_str(&format!("\nResultado: {}", p.resultado.trim()));
+        }
+        lineas.push(format!("DESCRIPTION:{}", escapar(&descripcion)));
+        if !p.enlace.is_empty() {
+            lineas.push(format!("URL:{}", p.enlace));
+        }
+        lineas.push("END:VEVENT".to_string());
+        for linea in lineas {
+            ics.push_str(&plegar(&linea));
+        }
+    }
+    ics.push_str(&plegar("END:VCALENDAR"));
+    ics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn un_evento_por_partido_con_fecha() {
+        let partido = Partido {
+            competicion: "BCN BENJAMÍ OR P4, GRUP B".to_string(),
+            data: "25/10/2026".to_string(),
+            hora: "10:00".to_string(),
+            local: "CH SANT CELONI".to_string(),
+            visitante: "CP VIC".to_string(),
+            pista: "PAV. MUNICIPAL SANT CELONI".to_string(),
+            ..Default::default()
+        };
+        let sin_hora = Partido {
+            hora: "".to_string(),
+            local: "CP MANLLEU".to_string(),
+            ..partido.clone()
+        };
+        let sin_fecha = Partido {
+            data: "APLAZADO".to_string(),
+            ..partido.clone()
+        };
+        let ics = a_ics(&[partido, sin_hora, sin_fecha]);
+
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ics.contains("DTSTART:20261025T100000\r\n"));
+        assert!(ics.contains("DTEND:20261025T113000\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20261025\r\n"));
+        assert!(ics.contains("SUMMARY:CH SANT CELONI - CP VIC\r\n"));
+        assert!(ics.contains("DESCRIPTION:BCN BENJAMÍ OR P4\\, GRUP B\r\n"));
+        assert!(ics.lines().all(|l| l.len() <= 75));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn los_cruces_repetidos_tienen_uid_distinto() {
+        let ida = Partido {
+            competicion: "OK LLIGA PLAYOFF".to_string(),
+            data: "02/05/2026".to_string(),
+            local: "CP VIC".to_string(),
+            visitante: "CE NOIA".to_string(),
+            ..Default::default()
+        };
+        let desempate = Partido {
+            data: "16/05/2026".to_string(),
+            ..ida.clone()
+        };
+        let ics = a_ics(&[ida, desempate]);
+        let uids: Vec<&str> = ics.lines().filter(|l| l.starts_with("UID:")).collect();
+
+        assert_eq!(uids.len(), 2);
+        assert_ne!(uids[0], uids[1]);
+    }
+}
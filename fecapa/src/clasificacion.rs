@@ -0,0 +1,314 @@
+use crate::estadisticas::{Balance, PUNTOS_EMPATE, PUNTOS_VICTORIA};
+use crate::models::Partido;
+use crate::ratings::Ratings;
+use std::collections::{BTreeSet, HashMap};
+
+/// Temporadas simuladas para la proyección de Monte Carlo.
+pub const SIMULACIONES: usize = 5000;
+
+#[derive(Debug, Clone)]
+pub struct FilaClasificacion {
+    pub equipo: String,
+    pub balance: Balance,
+}
+
+impl FilaClasificacion {
+    pub fn diferencia(&self) -> i64 {
+        self.balance.goles_favor as i64 - self.balance.goles_contra as i64
+    }
+}
+
+pub fn competiciones(todos: &[Partido]) -> Vec<String> {
+    todos
+        .iter()
+        .map(|p| p.competicion.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+fn ordenar(filas: &mut [FilaClasificacion]) {
+    filas.sort_by(|a, b| {
+        b.balance
+            .puntos()
+            .cmp(&a.balance.puntos())
+            .then(b.diferencia().cmp(&a.diferencia()))
+            .then(b.balance.goles_favor.cmp(&a.balance.goles_favor))
+            .then(a.equipo.cmp(&b.equipo))
+    });
+}
+
+/// Clasificación de una competición con los partidos que cumplen `incluir`.
+pub fn clasificacion_con(
+    todos: &[Partido],
+    competicion: &str,
+    incluir: impl Fn(&Partido) -> bool,
+) -> Vec<FilaClasificacion> {
+    let mut filas: HashMap<String, FilaClasificacion> = HashMap::new();
+    for p in todos.iter().filter(|p| p.competicion == competicion) {
+        for equipo in [&p.local, &p.visitante] {
+            filas
+                .entry(equipo.trim().to_uppercase())
+                .or_insert_with(|| FilaClasificacion {
+                    equipo: equipo.trim().to_string(),
+                    balance: Balance::default(),
+                });
+        }
+        if !incluir(p) {
+            continue;
+        }
+        if let Some((l, v)) = p.goles() {
+            if let Some(fila) = filas.get_mut(&p.local.trim().to_uppercase()) {
+                fila.balance.sumar(l, v);
+            }
+            if let Some(fila) = filas.get_mut(&p.visitante.trim().to_uppercase()) {
+                fila.balance.sumar(v, l);
+            }
+        }
+    }
+    let mut filas: Vec<FilaClasificacion> = filas.into_values().collect();
+    ordenar(&mut filas);
+    filas
+}
+
+pub fn clasificacion(todos: &[Partido], competicion: &str) -> Vec<FilaClasificacion> {
+    clasificacion_con(todos, competicion, |_| true)
+}
+
+#[derive(Debug, Clone)]
+pub struct ProyeccionEquipo {
+    pub equipo: String,
+    pub puntos: u32,
+    pub pendientes: u32,
+    pub puntos_maximos: u32,
+    pub mejor_posicion: usize,
+    pub peor_posicion: usize,
+    /// Puntos que necesita sumar para ser primero pase lo que pase.
+    pub puntos_para_titulo: Option<u32>,
+    pub prob_primero: f64,
+    pub prob_ultimo: f64,
+    pub posicion_media: f64,
+}
+
+impl ProyeccionEquipo {
+    pub fn estado(&self) -> &'static str {
+        if self.peor_posicion == 1 {
+            "Campeón asegurado"
+        } else if self.mejor_posicion > 1 {
+            "Sin opciones al título"
+        } else if self.puntos_para_titulo.is_some() {
+            "Depende de sí mismo"
+        } else {
+            "Depende de otros"
+        }
+    }
+}
+
+/// Generador pseudoaleatorio xorshift: suficiente para simular y evita
+/// añadir dependencias.
+struct Aleatorio(u64);
+
+impl Aleatorio {
+    fn nuevo() -> Self {
+        let semilla = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0x2545_f491_4f6c_dd1d);
+        Aleatorio(semilla | 1)
+    }
+
+    fn siguiente(&mut self) -> f64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Posiciones posibles, condiciones de título y simulación de Monte Carlo
+/// del resto de la competición usando el modelo de pronósticos Elo.
+pub fn proyeccion(
+    todos: &[Partido],
+    competicion: &str,
+    ratings: &Ratings,
+) -> Vec<ProyeccionEquipo> {
+    proyeccion_con(todos, competicion, ratings, Aleatorio::nuevo())
+}
+
+/// Como `proyeccion`, con el generador dado (las pruebas fijan la semilla).
+fn proyeccion_con(
+    todos: &[Partido],
+    competicion: &str,
+    ratings: &Ratings,
+    mut aleatorio: Aleatorio,
+) -> Vec<ProyeccionEquipo> {
+    let tabla = clasificacion(todos, competicion);
+    let indices: HashMap<String, usize> = tabla
+        .iter()
+        .enumerate()
+        .map(|(i, f)| (f.equipo.to_uppercase(), i))
+        .collect();
+    let pendientes: Vec<(&Partido, usize, usize)> = todos
+        .iter()
+        .filter(|p| p.competicion == competicion && p.goles().is_none())
+        .filter_map(|p| {
+            Some((
+                p,
+                *indices.get(&p.local.trim().to_uppercase())?,
+                *indices.get(&p.visitante.trim().to_uppercase())?,
+            ))
+        })
+        .collect();
+
+    let puntos: Vec<u32> = tabla.iter().map(|f| f.balance.puntos()).collect();
+    let mut restantes = vec![0u32; tabla.len()];
+    for (_, l, v) in &pendientes {
+        restantes[*l] += 1;
+        restantes[*v] += 1;
+    }
+    let maximos: Vec<u32> = puntos
+        .iter()
+        .zip(&restantes)
+        .map(|(p, r)| p + r * PUNTOS_VICTORIA)
+        .collect();
+
+    let mut primero = vec![0usize; tabla.len()];
+    let mut ultimo = vec![0usize; tabla.len()];
+    let mut suma_posiciones = vec![0usize; tabla.len()];
+    let pronosticos: Vec<_> = pendientes
+        .iter()
+        .map(|(p, l, v)| (ratings.pronostico(p), *l, *v))
+        .collect();
+    let simulaciones = if tabla.is_empty() { 0 } else { SIMULACIONES };
+
+    for _ in 0..simulaciones {
+        let mut finales = puntos.clone();
+        for (pronostico, l, v) in &pronosticos {
+            let x = aleatorio.siguiente();
+            if x < pronostico.local {
+                finales[*l] += PUNTOS_VICTORIA;
+            } else if x < pronostico.local + pronostico.empate {
+                finales[*l] += PUNTOS_EMPATE;
+                finales[*v] += PUNTOS_EMPATE;
+            } else {
+                finales[*v] += PUNTOS_VICTORIA;
+            }
+        }
+        // Desempate aleatorio: no se simulan goles.
+        let mut orden: Vec<(u32, f64, usize)> = finales
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (*p, aleatorio.siguiente(), i))
+            .collect();
+        orden.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.total_cmp(&b.1)));
+        for (posicion, (_, _, i)) in orden.iter().enumerate() {
+            suma_posiciones[*i] += posicion + 1;
+        }
+        primero[orden[0].2] += 1;
+        ultimo[orden[orden.len() - 1].2] += 1;
+    }
+
+    tabla
+        .iter()
+        .enumerate()
+        .map(|(i, fila)| {
+            let otros = (0..tabla.len()).filter(|j| *j != i);
+            let mejor = 1 + otros.clone().filter(|j| puntos[*j] > maximos[i]).count();
+            let peor = 1 + otros.clone().filter(|j| maximos[*j] >= puntos[i]).count();
+            let rival_maximo = otros.map(|j| maximos[j]).max().unwrap_or(0);
+            let necesarios = (rival_maximo + 1).saturating_sub(puntos[i]);
+            let n = simulaciones.max(1) as f64;
+            ProyeccionEquipo {
+                equipo: fila.equipo.clone(),
+                puntos: puntos[i],
+                pendientes: restantes[i],
+                puntos_maximos: maximos[i],
+                mejor_posicion: mejor,
+                peor_posicion: peor,
+                puntos_para_titulo: (necesarios <= restantes[i] * PUNTOS_VICTORIA)
+                    .then_some(necesarios),
+                prob_primero: primero[i] as f64 / n,
+                prob_ultimo: ultimo[i] as f64 / n,
+                posicion_media: suma_posiciones[i] as f64 / n,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMPETICION: &str = "BCN INFANTIL OR P4";
+
+    fn partido(local: &str, visitante: &str, resultado: &str) -> Partido {
+        Partido {
+            competicion: COMPETICION.to_string(),
+            data: "04/10/2025".to_string(),
+            local: local.to_string(),
+            visitante: visitante.to_string(),
+            resultado: resultado.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn proyectar(partidos: &[Partido]) -> Vec<ProyeccionEquipo> {
+        let ratings = Ratings::calcular(partidos);
+        proyeccion_con(
+            partidos,
+            COMPETICION,
+            &ratings,
+            Aleatorio(0x9e37_79b9_7f4a_7c15),
+        )
+    }
+
+    #[test]
+    fn una_competicion_acabada_queda_como_esta() {
+        let partidos = [
+            partido("A", "B", "3-1"),
+            partido("A", "C", "2-0"),
+            partido("B", "C", "4-4"),
+            partido("B", "A", "1-5"),
+            partido("C", "A", "0-1"),
+            partido("C", "B", "1-2"),
+        ];
+        let tabla = clasificacion(&partidos, COMPETICION);
+        let proyeccion = proyectar(&partidos);
+        assert_eq!(proyeccion.len(), 3);
+        for (i, (fila, equipo)) in tabla.iter().zip(&proyeccion).enumerate() {
+            assert_eq!(fila.equipo, equipo.equipo);
+            assert_eq!(equipo.pendientes, 0);
+            assert_eq!(equipo.puntos_maximos, equipo.puntos);
+            assert_eq!(
+                (equipo.mejor_posicion, equipo.peor_posicion),
+                (i + 1, i + 1)
+            );
+            assert_eq!(equipo.posicion_media, (i + 1) as f64);
+        }
+        assert_eq!(proyeccion[0].estado(), "Campeón asegurado");
+        assert_eq!(proyeccion[0].prob_primero, 1.0);
+        assert_eq!(proyeccion[2].prob_ultimo, 1.0);
+        assert_eq!(proyeccion[1].estado(), "Sin opciones al título");
+    }
+
+    #[test]
+    fn dos_equipos_con_un_partido_por_jugar() {
+        let partidos = [partido("A", "B", "3-1"), partido("B", "A", "")];
+        let proyeccion = proyectar(&partidos);
+        let (a, b) = (&proyeccion[0], &proyeccion[1]);
+        assert_eq!((a.equipo.as_str(), a.puntos, a.puntos_maximos), ("A", 3, 6));
+        assert_eq!((b.equipo.as_str(), b.puntos, b.puntos_maximos), ("B", 0, 3));
+        assert_eq!((a.mejor_posicion, a.peor_posicion), (1, 2));
+        assert_eq!((b.mejor_posicion, b.peor_posicion), (1, 2));
+        // A es campeón con un empate; B no puede pasar de los 6 de A.
+        assert_eq!(a.puntos_para_titulo, Some(1));
+        assert_eq!(b.puntos_para_titulo, None);
+        assert_eq!(a.estado(), "Depende de sí mismo");
+        assert_eq!(b.estado(), "Depende de otros");
+        assert!((a.prob_primero + b.prob_primero - 1.0).abs() < 1e-9);
+        assert!(a.prob_primero > 0.5);
+        assert!((a.posicion_media + b.posicion_media - 3.0).abs() < 1e-9);
+        // Con la misma semilla sale lo mismo.
+        assert_eq!(proyectar(&partidos)[0].prob_primero, a.prob_primero);
+    }
+}
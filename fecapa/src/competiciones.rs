@@ -0,0 +1,343 @@
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Campo {
+    Territorio,
+    Categoria,
+    Nivel,
+    Grupo,
+    Fase,
+}
+
+/// Regla de `competiciones.json`: si `patron` aparece en el nombre de la
+/// competición, `campo` toma `valor` (admite `$1`) o el texto encontrado.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Regla {
+    pub campo: Campo,
+    pub patron: String,
+    #[serde(default)]
+    pub valor: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConfigCompeticiones {
+    #[serde(default)]
+    pub reglas: Vec<Regla>,
+}
+
+const REGLAS_POR_DEFECTO: [(Campo, &str, &str); 26] = [
+    (Campo::Territorio, r"^BCN\b", "BCN"),
+    (Campo::Territorio, r"^GIR?\b", "GIR"),
+    (Campo::Territorio, r"^LLE?\b", "LLE"),
+    (Campo::Territorio, r"^(TGN|TAR)\b", "TGN"),
+    (Campo::Territorio, r"^CAT\b", "CAT"),
+    (Campo::Categoria, r"\bPRE-?BENJAM[IÍ]\b", "PREBENJAMÍ"),
+    (Campo::Categoria, r"\bBENJAM[IÍ]\b", "BENJAMÍ"),
+    (Campo::Categoria, r"\bALEV[IÍ]\b", "ALEVÍ"),
+    (Campo::Categoria, r"\bINFANTIL\b", "INFANTIL"),
+    (Campo::Categoria, r"\bJUVENIL\b", "JUVENIL"),
+    (Campo::Categoria, r"\bJ[UÚ]NIOR\b", "JÚNIOR"),
+    (Campo::Categoria, r"\bS[EÈÉ]NIOR\b", "SÈNIOR"),
+    (Campo::Categoria, r"\bVETERANS?\b", "VETERANS"),
+    (Campo::Categoria, r"\bFEM(?:EN[IÍ])?\s*(\d+)\b", "FEM $1"),
+    (Campo::Categoria, r"\bFEM(?:EN[IÍ])?\b", "FEMENÍ"),
+    (Campo::Nivel, r"\b(OR|PLATA|BRONZE|COURE)\b", "$1"),
+    (Campo::Nivel, r"\bPREFERENT\b", "PREFERENT"),
+    (Campo::Nivel, r"\b(PRIMERA|SEGONA|TERCERA)\b", "$1"),
+    (Campo::Nivel, r"\bOK\s+LLIGA\b", "OK LLIGA"),
+    (Campo::Grupo, r"\b(P\d+|CC\d+|G\d+)\b", "$1"),
+    (Campo::Grupo, r"\bGRUP\s+(\w+)\b", "GRUP $1"),
+    (Campo::Fase, r"\bFASE\s+(\w+)\b", "FASE $1"),
+    // Antes que FINAL, que con guion ("SEMI-FINAL") también encajaría.
+    (Campo::Fase, r"\bSEMI-?FINALS?\b", "SEMIFINALS"),
+    (Campo::Fase, r"\bFINALS?\b", "FINALS"),
+    (Campo::Fase, r"\bQUARTS\b", "QUARTS"),
+    (Campo::Fase, r"\bPLAY-?OFF\b", "PLAY-OFF"),
+];
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InfoCompeticion {
+    pub territorio: String,
+    pub categoria: String,
+    pub nivel: String,
+    pub grupo: String,
+    pub fase: String,
+}
+
+impl InfoCompeticion {
+    fn campo_mut(&mut self, campo: Campo) -> &mut String {
+        match campo {
+            Campo::Territorio => &mut self.territorio,
+            Campo::Categoria => &mut self.categoria,
+            Campo::Nivel => &mut self.nivel,
+            Campo::Grupo => &mut self.grupo,
+            Campo::Fase => &mut self.fase,
+        }
+    }
+}
+
+/// Reglas compiladas: primero las del usuario y después las de serie, de
+/// modo que el usuario puede corregir o ampliar cualquier campo.
+#[derive(Debug, Clone, Default)]
+pub struct Reglas {
+    reglas: Vec<(Campo, Regex, Option<String>)>,
+}
+
+impl Reglas {
+    pub fn nuevas(config: &ConfigCompeticiones) -> (Self, Vec<String>) {
+        let mut errores = Vec::new();
+        let usuario = config
+            .reglas
+            .iter()
+            .map(|r| (r.campo, r.patron.as_str(), r.valor.clone()));
+        let serie = REGLAS_POR_DEFECTO
+            .iter()
+            .map(|(c, p, v)| (*c, *p, Some(v.to_string())));
+
+        let reglas = usuario
+            .chain(serie)
+            .filter_map(|(campo, patron, valor)| {
+                match RegexBuilder::new(patron).case_insensitive(true).build() {
+                    Ok(re) => Some((campo, re, valor)),
+                    Err(e) => {
+                        errores.push(format!("Regla '{}' no válida: {}", patron, e));
+                        None
+                    }
+                }
+            })
+            .collect();
+        (Reglas { reglas }, errores)
+    }
+
+    pub fn parsear(&self, competicion: &str) -> InfoCompeticion {
+        let mut info = InfoCompeticion::default();
+        for (campo, re, valor) in &self.reglas {
+            if !info.campo_mut(*campo).is_empty() {
+                continue;
+            }
+            let Some(caps) = re.captures(competicion) else {
+                continue;
+            };
+            let mut texto = String::new();
+            match valor {
+                Some(valor) => caps.expand(valor, &mut texto),
+                None => texto.push_str(&caps[0]),
+            }
+            *info.campo_mut(*campo) = texto.trim().to_uppercase();
+        }
+        info
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NodoArbol {
+    pub profundidad: usize,
+    pub etiqueta: String,
+    pub ruta: String,
+    pub competiciones: Vec<String>,
+    pub expandido: bool,
+}
+
+impl NodoArbol {
+    pub fn es_hoja(&self) -> bool {
+        self.profundidad == 3 || (self.profundidad == 2 && self.competiciones.len() == 1)
+    }
+}
+
+fn o_sin(valor: &str, sin: &str) -> String {
+    if valor.is_empty() {
+        sin.to_string()
+    } else {
+        valor.to_string()
+    }
+}
+
+/// Filas visibles del árbol categoría → nivel → grupo. Un grupo con varias
+/// competiciones (p. ej. de distintos territorios) se despliega en ellas.
+pub fn arbol(
+    competiciones: &[String],
+    reglas: &Reglas,
+    expandidos: &HashSet<String>,
+) -> Vec<NodoArbol> {
+    type Grupos = BTreeMap<String, Vec<String>>;
+    let mut mapa: BTreeMap<String, BTreeMap<String, Grupos>> = BTreeMap::new();
+    for competicion in competiciones {
+        let info = reglas.parsear(competicion);
+        let grupo = if info.grupo.is_empty() {
+            o_sin(&info.fase, "(sin grupo)")
+        } else {
+            info.grupo
+        };
+        mapa.entry(o_sin(&info.categoria, "(sin categoría)"))
+            .or_default()
+            .entry(o_sin(&info.nivel, "(sin nivel)"))
+            .or_default()
+            .entry(grupo)
+            .or_default()
+            .push(competicion.clone());
+    }
+
+    let mut filas = Vec::new();
+    for (categoria, niveles) in mapa {
+        let ruta = categoria.clone();
+        let expandido = expandidos.contains(&ruta);
+        filas.push(NodoArbol {
+            profundidad: 0,
+            competiciones: niveles
+                .values()
+                .flat_map(|g| g.values().flatten())
+                .cloned()
+                .collect(),
+            etiqueta: categoria,
+            ruta: ruta.clone(),
+            expandido,
+        });
+        if !expandido {
+            continue;
+        }
+        for (nivel, grupos) in niveles {
+            let ruta = format!("{}/{}", ruta, nivel);
+            let expandido = expandidos.contains(&ruta);
+            filas.push(NodoArbol {
+                profundidad: 1,
+                competiciones: grupos.values().flatten().cloned().collect(),
+                etiqueta: nivel,
+                ruta: ruta.clone(),
+                expandido,
+            });
+            if !expandido {
+                continue;
+            }
+            for (grupo, competiciones) in grupos {
+                let ruta = format!("{}/{}", ruta, grupo);
+                let expandido = expandidos.contains(&ruta);
+                let varias = competiciones.len() > 1;
+                filas.push(NodoArbol {
+                    profundidad: 2,
+                    etiqueta: if varias {
+                        grupo
+                    } else {
+                        format!("{} ({})", grupo, competiciones[0])
+                    },
+                    competiciones: competiciones.clone(),
+                    ruta: ruta.clone(),
+                    expandido,
+                });
+                if varias && expandido {
+                    for competicion in competiciones {
+                        filas.push(NodoArbol {
+                            profundidad: 3,
+                            etiqueta: competicion.clone(),
+                            ruta: format!("{}/{}", ruta, competicion),
+                            competiciones: vec![competicion],
+                            expandido: false,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    filas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(
+        territorio: &str,
+        categoria: &str,
+        nivel: &str,
+        grupo: &str,
+        fase: &str,
+    ) -> InfoCompeticion {
+        InfoCompeticion {
+            territorio: territorio.to_string(),
+            categoria: categoria.to_string(),
+            nivel: nivel.to_string(),
+            grupo: grupo.to_string(),
+            fase: fase.to_string(),
+        }
+    }
+
+    #[test]
+    fn las_reglas_de_serie_entienden_los_nombres_de_la_fecapa() {
+        let (reglas, errores) = Reglas::nuevas(&ConfigCompeticiones::default());
+        assert!(errores.is_empty(), "{:?}", errores);
+        let casos = [
+            ("BCN BENJAMÍ OR P4", info("BCN", "BENJAMÍ", "OR", "P4", "")),
+            (
+                "BCN PRE-BENJAMÍ PLATA P12",
+                info("BCN", "PREBENJAMÍ", "PLATA", "P12", ""),
+            ),
+            (
+                "GI ALEVÍ BRONZE GRUP B",
+                info("GIR", "ALEVÍ", "BRONZE", "GRUP B", ""),
+            ),
+            (
+                "CAT INFANTIL OR FASE FINAL",
+                info("CAT", "INFANTIL", "OR", "", "FASE FINAL"),
+            ),
+            (
+                "CAT JUVENIL PREFERENT SEMIFINALS",
+                info("CAT", "JUVENIL", "PREFERENT", "", "SEMIFINALS"),
+            ),
+            (
+                "CAT JÚNIOR OR SEMI-FINAL",
+                info("CAT", "JÚNIOR", "OR", "", "SEMIFINALS"),
+            ),
+            (
+                "CAT JÚNIOR OR FINALS",
+                info("CAT", "JÚNIOR", "OR", "", "FINALS"),
+            ),
+            (
+                "OK LLIGA PLATA FEMENÍ PLAY-OFF",
+                info("", "FEMENÍ", "PLATA", "", "PLAY-OFF"),
+            ),
+            ("CAT FEM 15 CC2", info("CAT", "FEM 15", "", "CC2", "")),
+            (
+                "TAR SÈNIOR SEGONA QUARTS",
+                info("TGN", "SÈNIOR", "SEGONA", "", "QUARTS"),
+            ),
+            ("LLEIDA VETERANS", info("", "VETERANS", "", "", "")),
+        ];
+        for (competicion, esperado) in casos {
+            assert_eq!(reglas.parsear(competicion), esperado, "{}", competicion);
+        }
+    }
+
+    #[test]
+    fn las_reglas_del_usuario_van_antes() {
+        let regla = |campo, patron: &str, valor: Option<&str>| Regla {
+            campo,
+            patron: patron.to_string(),
+            valor: valor.map(str::to_string),
+        };
+        let config = ConfigCompeticiones {
+            reglas: vec![
+                regla(Campo::Nivel, r"\bOR\b", Some("A")),
+                regla(Campo::Categoria, r"\bESCOLA\b", Some("PREBENJAMÍ")),
+                regla(Campo::Grupo, r"(", None),
+                regla(Campo::Territorio, r"^MAR\b", None),
+            ],
+        };
+        let (reglas, errores) = Reglas::nuevas(&config);
+        assert_eq!(errores.len(), 1);
+        assert!(
+            errores[0].starts_with("Regla '(' no válida"),
+            "{}",
+            errores[0]
+        );
+        assert_eq!(
+            reglas.parsear("BCN BENJAMÍ OR P4"),
+            info("BCN", "BENJAMÍ", "A", "P4", "")
+        );
+        assert_eq!(
+            reglas.parsear("mar escola plata p2"),
+            info("MAR", "PREBENJAMÍ", "PLATA", "P2", "")
+        );
+    }
+}
@@ -0,0 +1,1001 @@
+use crate::competiciones::ConfigCompeticiones;
+use crate::esquema::ArchivoPartidos;
+use crate::fuentes::Ajustes;
+use crate::models::{
+    Acta, Anotaciones, Asistencia, ConfigPistas, HistoriaPartido, Origen, Partido,
+};
+use crate::models::{EquipoConfig, Filtro};
+use crate::temporadas;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ffi::OsString;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+pub const NOMBRE_APP: &str = "fecapa-explorer";
+
+/// Archivos que van al directorio de configuración; el resto son datos.
+const ARCHIVOS_CONFIG: [&str; 5] = [
+    "equipos.json",
+    "ajustes.json",
+    "competiciones.json",
+    "pistas.json",
+    "teclas.json",
+];
+
+/// Datos que se copian desde el directorio del ejecutable o el actual la
+/// primera vez que se usan los directorios XDG.
+const ARCHIVOS_DATOS: [&str; 7] = [
+    "partidos.json",
+    "partidos_manuales.json",
+    "favoritos.json",
+    "asistencia.json",
+    "actas.json",
+    "historico.json",
+    "temporadas",
+];
+
+const MARCA_MIGRACION: &str = ".migrado";
+
+#[derive(Debug, Clone)]
+pub struct Directorios {
+    pub config: PathBuf,
+    pub datos: PathBuf,
+    pub cache: PathBuf,
+    pub perfil: Option<String>,
+    /// Directorios elegidos con `--data-dir` o `FECAPA_DATA_DIR`.
+    pub forzados: bool,
+}
+
+static DIRECTORIOS: OnceLock<Directorios> = OnceLock::new();
+
+/// Antes de XDG los archivos iban junto al ejecutable si ya estaban allí y
+/// si no en el directorio actual.
+fn directorio_heredado() -> PathBuf {
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|p| p.parent().map(|p| p.to_path_buf()))
+        .unwrap_or_default();
+
+    let current_dir = std::env::current_dir().unwrap_or_default();
+
+    if exe_dir.join("partidos.json").exists() && exe_dir.join("equipos.json").exists() {
+        return exe_dir;
+    }
+
+    current_dir
+}
+
+/// Lee una variable de entorno; las pruebas usan uno inventado.
+type Entorno<'a> = &'a dyn Fn(&str) -> Option<OsString>;
+
+/// `$XDG_*_HOME/fecapa-explorer` o, si no está definida, `~/<por_defecto>`.
+fn xdg(entorno: Entorno, variable: &str, por_defecto: &str) -> Option<PathBuf> {
+    entorno(variable)
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| entorno("HOME").map(|home| PathBuf::from(home).join(por_defecto)))
+        .map(|p| p.join(NOMBRE_APP))
+}
+
+fn calcular_directorios(
+    data_dir: Option<PathBuf>,
+    perfil: Option<String>,
+    entorno: Entorno,
+    heredado: &dyn Fn() -> PathBuf,
+) -> Directorios {
+    let data_dir = data_dir.or_else(|| entorno("FECAPA_DATA_DIR").map(PathBuf::from));
+    let perfil = perfil
+        .or_else(|| entorno("FECAPA_PERFIL").and_then(|p| p.into_string().ok()))
+        .filter(|p| !p.trim().is_empty());
+    let forzados = data_dir.is_some();
+
+    let (config, datos, cache) = match data_dir {
+        Some(dir) => (dir.clone(), dir.clone(), dir.join("cache")),
+        None => match (
+            xdg(entorno, "XDG_CONFIG_HOME", ".config"),
+            xdg(entorno, "XDG_DATA_HOME", ".local/share"),
+            xdg(entorno, "XDG_CACHE_HOME", ".cache"),
+        ) {
+            (Some(config), Some(datos), Some(cache)) => (config, datos, cache),
+            // Sin HOME (algunos entornos Android) se sigue como antes.
+            _ => {
+                let dir = heredado();
+                (dir.clone(), dir.clone(), dir.join("cache"))
+            }
+        },
+    };
+
+    let (config, datos, cache) = match &perfil {
+        Some(p) => (
+            config.join("perfiles").join(p),
+            datos.join("perfiles").join(p),
+            cache.join("perfiles").join(p),
+        ),
+        None => (config, datos, cache),
+    };
+
+    Directorios {
+        config,
+        datos,
+        cache,
+        perfil,
+        forzados,
+    }
+}
+
+impl Directorios {
+    /// Ruta de un archivo de la aplicación: los de configuración en el
+    /// directorio de configuración y el resto en el de datos.
+    pub fn ruta(&self, nombre: &str) -> PathBuf {
+        if ARCHIVOS_CONFIG.contains(&nombre) {
+            self.config.join(nombre)
+        } else {
+            self.datos.join(nombre)
+        }
+    }
+
+    /// Copia una sola vez los archivos de `origen` a estos directorios. No
+    /// borra los originales ni sobrescribe nada que ya exista. Devuelve los
+    /// archivos copiados.
+    fn migrar_desde(&self, origen: &Path) -> Vec<String> {
+        let marca = self.datos.join(MARCA_MIGRACION);
+        if self.forzados || self.perfil.is_some() || marca.exists() {
+            return Vec::new();
+        }
+
+        let mut copiados = Vec::new();
+        for nombre in ARCHIVOS_CONFIG.iter().chain(ARCHIVOS_DATOS.iter()) {
+            let desde = origen.join(nombre);
+            let hacia = self.ruta(nombre);
+            if desde.exists() && !hacia.exists() && copiar_recursivo(&desde, &hacia).is_ok() {
+                copiados.push(nombre.to_string());
+            }
+        }
+
+        let _ = fs::create_dir_all(&self.datos);
+        let _ = fs::write(
+            marca,
+            format!("Archivos copiados desde {}\n", origen.display()),
+        );
+        copiados
+    }
+
+    /// Perfiles creados hasta ahora (subdirectorios de `perfiles/`).
+    pub fn perfiles(&self) -> Vec<String> {
+        let base = match &self.perfil {
+            Some(_) => self
+                .config
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default(),
+            None => self.config.join("perfiles"),
+        };
+        let mut perfiles: Vec<String> = fs::read_dir(base)
+            .map(|entradas| {
+                entradas
+                    .flatten()
+                    .filter(|e| e.path().is_dir())
+                    .filter_map(|e| e.file_name().into_string().ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        perfiles.sort();
+        perfiles
+    }
+}
+
+/// Fija los directorios a partir de `--data-dir` y `--perfil`. Debe llamarse
+/// antes de leer o escribir cualquier archivo; si no, se usan las variables
+/// de entorno y XDG.
+pub fn configurar_directorios(data_dir: Option<PathBuf>, perfil: Option<String>) {
+    let _ = DIRECTORIOS.set(calcular_directorios(
+        data_dir,
+        perfil,
+        &|variable| std::env::var_os(variable),
+        &directorio_heredado,
+    ));
+}
+
+pub fn directorios() -> &'static Directorios {
+    DIRECTORIOS.get_or_init(|| {
+        calcular_directorios(
+            None,
+            None,
+            &|variable| std::env::var_os(variable),
+            &directorio_heredado,
+        )
+    })
+}
+
+pub fn get_data_dir() -> PathBuf {
+    directorios().datos.clone()
+}
+
+pub fn get_cache_dir() -> PathBuf {
+    directorios().cache.clone()
+}
+
+/// Ruta de un archivo de la aplicación: los de configuración en el
+/// directorio de configuración y el resto en el de datos. No crea nada: el
+/// directorio se crea al escribir.
+pub fn find_file_path(filename: &str) -> PathBuf {
+    directorios().ruta(filename)
+}
+
+/// Busca un script que acompaña al programa (junto a la configuración, al
+/// ejecutable o en el directorio actual).
+pub fn buscar_script(nombre: &str) -> PathBuf {
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|p| p.parent().map(|p| p.to_path_buf()));
+    [
+        Some(directorios().config.clone()),
+        exe_dir,
+        std::env::current_dir().ok(),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|dir| dir.join(nombre))
+    .find(|ruta| ruta.exists())
+    .unwrap_or_else(|| PathBuf::from(nombre))
+}
+
+fn copiar_recursivo(origen: &Path, destino: &Path) -> std::io::Result<()> {
+    if origen.is_dir() {
+        fs::create_dir_all(destino)?;
+        for entrada in fs::read_dir(origen)? {
+            let entrada = entrada?;
+            copiar_recursivo(&entrada.path(), &destino.join(entrada.file_name()))?;
+        }
+        Ok(())
+    } else {
+        if let Some(dir) = destino.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::copy(origen, destino).map(|_| ())
+    }
+}
+
+/// Copia una sola vez los archivos de la ubicación antigua a los directorios
+/// XDG. Devuelve los archivos copiados.
+pub fn migrar_archivos() -> Vec<String> {
+    directorios().migrar_desde(&directorio_heredado())
+}
+
+/// Perfiles creados hasta ahora.
+pub fn listar_perfiles() -> Vec<String> {
+    directorios().perfiles()
+}
+
+/// Error al leer o escribir un archivo de la aplicación.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorDatos {
+    Lectura {
+        ruta: PathBuf,
+        error: String,
+    },
+    /// JSON mal formado, con la posición que indica serde.
+    Formato {
+        ruta: PathBuf,
+        linea: usize,
+        columna: usize,
+        error: String,
+    },
+    Escritura {
+        ruta: PathBuf,
+        error: String,
+    },
+    /// El archivo no se pudo cargar y guardarlo ahora borraría su contenido.
+    Bloqueado {
+        ruta: PathBuf,
+    },
+    /// El archivo se leyó, pero algunas entradas no valen y se ignoran.
+    Contenido {
+        ruta: PathBuf,
+        problemas: Vec<String>,
+    },
+}
+
+impl ErrorDatos {
+    pub fn ruta(&self) -> &Path {
+        match self {
+            ErrorDatos::Lectura { ruta, .. }
+            | ErrorDatos::Formato { ruta, .. }
+            | ErrorDatos::Escritura { ruta, .. }
+            | ErrorDatos::Bloqueado { ruta }
+            | ErrorDatos::Contenido { ruta, .. } => ruta,
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorDatos {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorDatos::Lectura { ruta, error } => {
+                write!(f, "No se pudo leer {}: {}", ruta.display(), error)
+            }
+            ErrorDatos::Formato {
+                ruta,
+                linea,
+                columna,
+                error,
+            } => write!(
+                f,
+                "{}: JSON no válido en la línea {}, columna {}: {}",
+                ruta.display(),
+                linea,
+                columna,
+                error
+            ),
+            ErrorDatos::Escritura { ruta, error } => {
+                write!(f, "No se pudo guardar {}: {}", ruta.display(), error)
+            }
+            ErrorDatos::Bloqueado { ruta } => write!(
+                f,
+                "{} tiene errores; no se sobrescribe para no perder datos",
+                ruta.display()
+            ),
+            ErrorDatos::Contenido { ruta, problemas } => {
+                write!(f, "{}: ", ruta.display())?;
+                match problemas.as_slice() {
+                    [problema] => write!(f, "{}", problema),
+                    [primero, resto @ ..] => write!(f, "{} (y {} más)", primero, resto.len()),
+                    [] => write!(f, "entradas no válidas"),
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for ErrorDatos {}
+
+/// Archivos que no se pudieron interpretar: no se escriben hasta que se
+/// apartan con `apartar_archivo` o se vuelven a leer sin errores.
+static BLOQUEADOS: Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+
+fn bloqueados() -> std::sync::MutexGuard<'static, BTreeSet<PathBuf>> {
+    BLOQUEADOS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Lee un JSON; si el archivo no existe devuelve `None`.
+fn leer_json<T: DeserializeOwned>(ruta: &Path) -> Result<Option<T>, ErrorDatos> {
+    // Con el cerrojo compartido no se lee a medias lo que otra instancia
+    // está escribiendo. Si no se puede crear (directorio de solo lectura) se
+    // lee igualmente.
+    let _cerrojo = ruta.exists().then(|| bloquear(ruta, false).ok());
+    let data = match fs::read_to_string(ruta) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(ErrorDatos::Lectura {
+                ruta: ruta.to_path_buf(),
+                error: e.to_string(),
+            })
+        }
+    };
+    match serde_json::from_str(&data) {
+        Ok(valor) => {
+            bloqueados().remove(ruta);
+            Ok(Some(valor))
+        }
+        Err(e) => {
+            bloqueados().insert(ruta.to_path_buf());
+            let posicion = format!(" at line {} column {}", e.line(), e.column());
+            Err(ErrorDatos::Formato {
+                ruta: ruta.to_path_buf(),
+                linea: e.line(),
+                columna: e.column(),
+                error: e.to_string().trim_end_matches(&posicion).to_string(),
+            })
+        }
+    }
+}
+
+fn escribir_json<T: Serialize + ?Sized>(ruta: &Path, valor: &T) -> Result<(), ErrorDatos> {
+    escribir_json_con(directorios(), ruta, valor)
+}
+
+/// `escribir_json` guardando las copias en los directorios `dirs`.
+fn escribir_json_con<T: Serialize + ?Sized>(
+    dirs: &Directorios,
+    ruta: &Path,
+    valor: &T,
+) -> Result<(), ErrorDatos> {
+    if bloqueados().contains(ruta) {
+        return Err(ErrorDatos::Bloqueado {
+            ruta: ruta.to_path_buf(),
+        });
+    }
+    let error = |e: String| ErrorDatos::Escritura {
+        ruta: ruta.to_path_buf(),
+        error: e,
+    };
+    let json = serde_json::to_string_pretty(valor).map_err(|e| error(e.to_string()))?;
+    crear_directorio_de(ruta).map_err(|e| error(e.to_string()))?;
+    let _cerrojo = bloquear(ruta, true).map_err(|e| error(e.to_string()))?;
+    if fs::read(ruta).is_ok_and(|actual| actual == json.as_bytes()) {
+        return Ok(());
+    }
+    dirs.rotar_copias(ruta)
+        .map_err(|e| error(format!("no se pudo guardar la copia: {}", e)))?;
+    escribir_atomico(ruta, json.as_bytes()).map_err(|e| error(e.to_string()))
+}
+
+fn crear_directorio_de(ruta: &Path) -> std::io::Result<()> {
+    match ruta.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => fs::create_dir_all(dir),
+        _ => Ok(()),
+    }
+}
+
+/// Abre (o crea) `.<nombre>.lock` junto al archivo y lo bloquea. El cerrojo
+/// se suelta al cerrar el `File`, así que basta con mantenerlo vivo mientras
+/// dura la lectura o escritura. Es un archivo aparte porque el renombrado
+/// de `escribir_atomico` cambia el inodo del propio archivo.
+fn bloquear(ruta: &Path, exclusivo: bool) -> std::io::Result<fs::File> {
+    let nombre = ruta.file_name().unwrap_or_default().to_string_lossy();
+    let cerrojo = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(ruta.with_file_name(format!(".{}.lock", nombre)))?;
+    if exclusivo {
+        cerrojo.lock()?;
+    } else {
+        cerrojo.lock_shared()?;
+    }
+    Ok(cerrojo)
+}
+
+/// Escribe en un temporal del mismo directorio y lo renombra encima del
+/// original: si el programa se corta a medias el archivo queda entero, con
+/// la versión nueva o con la anterior.
+pub(crate) fn escribir_atomico(ruta: &Path, contenido: &[u8]) -> std::io::Result<()> {
+    let nombre = ruta.file_name().unwrap_or_default().to_string_lossy();
+    let temporal = ruta.with_file_name(format!(".{}.tmp-{}", nombre, std::process::id()));
+    let resultado = (|| {
+        let mut archivo = fs::File::create(&temporal)?;
+        archivo.write_all(contenido)?;
+        archivo.sync_all()?;
+        fs::rename(&temporal, ruta)
+    })();
+    if resultado.is_err() {
+        let _ = fs::remove_file(&temporal);
+    }
+    resultado
+}
+
+/// Versiones anteriores que se guardan de cada archivo con copias.
+pub const COPIAS_MAXIMAS: usize = 5;
+
+/// Archivos de los que se guardan copias antes de cada escritura: los que
+/// cuesta rehacer a mano o volver a descargar.
+pub const ARCHIVOS_CON_COPIAS: [&str; 5] = [
+    "partidos.json",
+    "equipos.json",
+    "partidos_manuales.json",
+    "favoritos.json",
+    "asistencia.json",
+];
+
+impl Directorios {
+    fn ruta_copia(&self, nombre: &str, numero: usize) -> PathBuf {
+        self.datos
+            .join("copias")
+            .join(format!("{}.{}", nombre, numero))
+    }
+
+    /// Desplaza `copias/<nombre>.1..N` una posición (la más antigua se
+    /// pierde) y copia la versión actual como `.1`.
+    fn rotar_copias(&self, ruta: &Path) -> std::io::Result<()> {
+        let nombre = ruta.file_name().unwrap_or_default().to_string_lossy();
+        if !ARCHIVOS_CON_COPIAS.contains(&nombre.as_ref()) || !ruta.exists() {
+            return Ok(());
+        }
+        fs::create_dir_all(self.datos.join("copias"))?;
+        for numero in (1..COPIAS_MAXIMAS).rev() {
+            let origen = self.ruta_copia(&nombre, numero);
+            if origen.exists() {
+                fs::rename(&origen, self.ruta_copia(&nombre, numero + 1))?;
+            }
+        }
+        fs::copy(ruta, self.ruta_copia(&nombre, 1))?;
+        Ok(())
+    }
+
+    /// Copias disponibles de `nombre`, de la más reciente (1) a la más
+    /// antigua.
+    pub fn listar_copias(
+        &self,
+        nombre: &str,
+    ) -> Vec<(usize, PathBuf, Option<std::time::SystemTime>)> {
+        (1..=COPIAS_MAXIMAS)
+            .map(|numero| (numero, self.ruta_copia(nombre, numero)))
+            .filter(|(_, ruta)| ruta.exists())
+            .map(|(numero, ruta)| {
+                let modificado = fs::metadata(&ruta).and_then(|m| m.modified()).ok();
+                (numero, ruta, modificado)
+            })
+            .collect()
+    }
+
+    /// Vuelve a poner la copia `numero` de `nombre` en su sitio. La versión
+    /// actual pasa a ser la copia 1, así que restaurar también se puede
+    /// deshacer.
+    pub fn restaurar_copia(&self, nombre: &str, numero: usize) -> Result<PathBuf, ErrorDatos> {
+        let copia = self.ruta_copia(nombre, numero);
+        let contenido = fs::read(&copia).map_err(|e| ErrorDatos::Lectura {
+            ruta: copia.clone(),
+            error: e.to_string(),
+        })?;
+        if let Err(e) = serde_json::from_slice::<serde_json::Value>(&contenido) {
+            return Err(ErrorDatos::Formato {
+                ruta: copia,
+                linea: e.line(),
+                columna: e.column(),
+                error: e.to_string(),
+            });
+        }
+
+        let ruta = self.ruta(nombre);
+        let error = |e: std::io::Error| ErrorDatos::Escritura {
+            ruta: ruta.clone(),
+            error: e.to_string(),
+        };
+        crear_directorio_de(&ruta).map_err(error)?;
+        let _cerrojo = bloquear(&ruta, true).map_err(error)?;
+        // Se lee antes de rotar porque la rotación mueve la copia de sitio.
+        self.rotar_copias(&ruta).map_err(error)?;
+        escribir_atomico(&ruta, &contenido).map_err(error)?;
+        bloqueados().remove(&ruta);
+        Ok(ruta)
+    }
+}
+
+/// Copias disponibles de `nombre` en los directorios de la aplicación.
+pub fn listar_copias(nombre: &str) -> Vec<(usize, PathBuf, Option<std::time::SystemTime>)> {
+    directorios().listar_copias(nombre)
+}
+
+/// Restaura la copia `numero` de `nombre` en los directorios de la
+/// aplicación.
+pub fn restaurar_copia(nombre: &str, numero: usize) -> Result<PathBuf, ErrorDatos> {
+    directorios().restaurar_copia(nombre, numero)
+}
+
+/// Renombra un archivo con errores a `<nombre>.corrupto-<fecha>` para que
+/// se pueda volver a guardar sin perder el original.
+pub fn apartar_archivo(ruta: &Path) -> Result<PathBuf, ErrorDatos> {
+    let mut copia = ruta.as_os_str().to_owned();
+    copia.push(format!(
+        ".corrupto-{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    let copia = PathBuf::from(copia);
+    fs::rename(ruta, &copia).map_err(|e| ErrorDatos::Escritura {
+        ruta: copia.clone(),
+        error: e.to_string(),
+    })?;
+    bloqueados().remove(ruta);
+    Ok(copia)
+}
+
+pub fn cargar_filtros() -> Result<Vec<Filtro>, ErrorDatos> {
+    let config: Option<EquipoConfig> = leer_json(&find_file_path("equipos.json"))?;
+    Ok(match config {
+        Some(config) => config.filtros,
+        None => vec![Filtro {
+            nombre: "Todos".to_string(),
+            buscar: "".to_string(),
+            categoria: "".to_string(),
+            favoritos: false,
+        }],
+    })
+}
+
+pub fn guardar_filtros(filtros: &[Filtro]) -> Result<(), ErrorDatos> {
+    let config = EquipoConfig {
+        filtros: filtros.to_vec(),
+    };
+    escribir_json(&find_file_path("equipos.json"), &config)
+}
+
+pub fn cargar_partidos() -> Result<ArchivoPartidos, ErrorDatos> {
+    let ruta = find_file_path("partidos.json");
+    let mut archivo: ArchivoPartidos = leer_json(&ruta)?.unwrap_or_default();
+    // Los archivos antiguos no dicen cuándo se descargaron; la fecha de
+    // modificación es la mejor aproximación.
+    if archivo.actualizado().is_none() {
+        archivo.descargado = fs::metadata(&ruta)
+            .and_then(|m| m.modified())
+            .ok()
+            .map(|m| chrono::DateTime::<chrono::Local>::from(m).to_rfc3339());
+    }
+    Ok(archivo)
+}
+
+pub fn guardar_partidos(archivo: &ArchivoPartidos) -> Result<(), ErrorDatos> {
+    escribir_json(&find_file_path("partidos.json"), archivo)
+}
+
+pub fn cargar_ajustes() -> Result<Ajustes, ErrorDatos> {
+    Ok(leer_json(&find_file_path("ajustes.json"))?.unwrap_or_default())
+}
+
+/// Atajos de teclado de `teclas.json`, sin interpretar: contexto → acción
+/// → teclas separadas por espacios. Vacío si no hay archivo.
+pub fn cargar_teclas() -> Result<BTreeMap<String, BTreeMap<String, String>>, ErrorDatos> {
+    Ok(leer_json(&find_file_path("teclas.json"))?.unwrap_or_default())
+}
+
+pub fn cargar_partidos_manuales() -> Result<Vec<Partido>, ErrorDatos> {
+    let mut partidos: Vec<Partido> =
+        leer_json(&find_file_path("partidos_manuales.json"))?.unwrap_or_default();
+    for p in partidos.iter_mut() {
+        p.origen = Origen::Manual;
+    }
+    Ok(partidos)
+}
+
+pub fn guardar_partidos_manuales(partidos: &[Partido]) -> Result<(), ErrorDatos> {
+    escribir_json(&find_file_path("partidos_manuales.json"), partidos)
+}
+
+pub fn cargar_anotaciones() -> Result<Anotaciones, ErrorDatos> {
+    Ok(leer_json(&find_file_path("favoritos.json"))?.unwrap_or_default())
+}
+
+pub fn guardar_anotaciones(anotaciones: &Anotaciones) -> Result<(), ErrorDatos> {
+    escribir_json(&find_file_path("favoritos.json"), anotaciones)
+}
+
+pub fn cargar_asistencia() -> Result<Asistencia, ErrorDatos> {
+    Ok(leer_json(&find_file_path("asistencia.json"))?.unwrap_or_default())
+}
+
+pub fn guardar_asistencia(asistencia: &Asistencia) -> Result<(), ErrorDatos> {
+    escribir_json(&find_file_path("asistencia.json"), asistencia)
+}
+
+pub fn cargar_pistas() -> Result<ConfigPistas, ErrorDatos> {
+    Ok(leer_json(&find_file_path("pistas.json"))?.unwrap_or_default())
+}
+
+pub fn cargar_config_competiciones() -> Result<ConfigCompeticiones, ErrorDatos> {
+    Ok(leer_json(&find_file_path("competiciones.json"))?.unwrap_or_default())
+}
+
+/// Actas por `Partido::clave_acta`. Las guardadas con claves de cruce
+/// (versiones anteriores) pasan a la URL del acta al leerlas.
+pub fn cargar_actas() -> Result<BTreeMap<String, Acta>, ErrorDatos> {
+    let actas: BTreeMap<String, Acta> =
+        leer_json(&find_file_path("actas.json"))?.unwrap_or_default();
+    Ok(actas
+        .into_iter()
+        .map(|(clave, acta)| {
+            if acta.url.is_empty() {
+                (clave, acta)
+            } else {
+                (acta.url.clone(), acta)
+            }
+        })
+        .collect())
+}
+
+pub fn guardar_actas(actas: &BTreeMap<String, Acta>) -> Result<(), ErrorDatos> {
+    escribir_json(&find_file_path("actas.json"), actas)
+}
+
+pub fn cargar_historico() -> Result<BTreeMap<String, HistoriaPartido>, ErrorDatos> {
+    Ok(leer_json(&find_file_path("historico.json"))?.unwrap_or_default())
+}
+
+pub fn guardar_historico(historico: &BTreeMap<String, HistoriaPartido>) -> Result<(), ErrorDatos> {
+    escribir_json(&find_file_path("historico.json"), historico)
+}
+
+/// Archivo de temporadas: un `partidos.json` por temporada en `temporadas/`.
+/// Las temporadas ilegibles se omiten y se devuelven sus errores.
+pub fn cargar_temporadas() -> (BTreeMap<String, Vec<Partido>>, Vec<ErrorDatos>) {
+    let dir = find_file_path("temporadas");
+    let mut temporadas = BTreeMap::new();
+    let mut errores = Vec::new();
+
+    if let Ok(entradas) = fs::read_dir(&dir) {
+        for entrada in entradas.flatten() {
+            let path = entrada.path();
+            if path.extension().is_none_or(|e| e != "json") {
+                continue;
+            }
+            let Some(nombre) = path.file_stem().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            match leer_json::<Vec<Partido>>(&path) {
+                Ok(partidos) => {
+                    temporadas.insert(nombre.to_string(), partidos.unwrap_or_default());
+                }
+                Err(e) => errores.push(e),
+            }
+        }
+    }
+    (temporadas, errores)
+}
+
+pub fn guardar_temporada(temporada: &str, partidos: &[Partido]) -> Result<(), ErrorDatos> {
+    let dir = find_file_path("temporadas");
+    escribir_json(&dir.join(format!("{}.json", temporada)), partidos)
+}
+
+/// El archivo de temporadas con los partidos de la agenda incorporados,
+/// sin escribir nada.
+pub fn cargar_temporadas_con(
+    partidos: &[Partido],
+) -> (BTreeMap<String, Vec<Partido>>, Vec<ErrorDatos>) {
+    let (mut archivo, errores) = cargar_temporadas();
+    temporadas::incorporar(&mut archivo, partidos);
+    (archivo, errores)
+}
+
+/// Guarda las temporadas del archivo; las que no han cambiado no se tocan.
+pub fn guardar_temporadas(archivo: &BTreeMap<String, Vec<Partido>>) -> Vec<ErrorDatos> {
+    archivo
+        .iter()
+        .filter_map(|(temporada, partidos)| guardar_temporada(temporada, partidos).err())
+        .collect()
+}
+
+/// Incorpora los partidos descargados al archivo y guarda las temporadas
+/// que han cambiado. Así, cuando la web pasa a la temporada siguiente, la
+/// anterior sigue disponible.
+pub fn archivar_temporadas(
+    partidos: &[Partido],
+) -> (BTreeMap<String, Vec<Partido>>, Vec<ErrorDatos>) {
+    let (archivo, mut errores) = cargar_temporadas_con(partidos);
+    errores.extend(guardar_temporadas(&archivo));
+    (archivo, errores)
+}
+
+pub fn exportar_texto(filename: &str, texto: &str) -> Result<PathBuf, String> {
+    let final_path = get_data_dir().join(filename);
+    crear_directorio_de(&final_path)
+        .and_then(|()| escribir_atomico(&final_path, texto.as_bytes()))
+        .map_err(|e| format!("Error escribiendo archivo: {}", e))?;
+    Ok(final_path)
+}
+
+pub fn is_android() -> bool {
+    std::env::consts::OS == "android"
+        || std::env::var("ANDROID_ROOT").is_ok()
+        || std::env::var("TERMUX_VERSION").is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pruebas::Temporal;
+
+    #[test]
+    fn un_json_roto_da_la_linea_y_la_columna_y_no_se_sobrescribe() {
+        let dir = Temporal::nuevo("datos-roto");
+        let ruta = dir.0.join("equipos.json");
+        fs::write(
+            &ruta,
+            "{\n  \"filtros\": [\n    {\"nombre\": \"Todos\",}\n  ]\n}",
+        )
+        .unwrap();
+
+        let error = leer_json::<EquipoConfig>(&ruta).unwrap_err();
+        assert!(matches!(
+            error,
+            ErrorDatos::Formato {
+                linea: 3,
+                columna: 24,
+                ..
+            }
+        ));
+        assert!(
+            error
+                .to_string()
+                .contains("línea 3, columna 24: trailing comma"),
+            "{}",
+            error
+        );
+        let error = escribir_json(&ruta, &EquipoConfig { filtros: vec![] }).unwrap_err();
+        assert_eq!(error, ErrorDatos::Bloqueado { ruta: ruta.clone() });
+        assert!(fs::read_to_string(&ruta).unwrap().contains("\"Todos\",}"));
+
+        // Una vez apartado se puede volver a guardar, y el original sigue ahí.
+        let copia = apartar_archivo(&ruta).unwrap();
+        assert!(copia
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("equipos.json.corrupto-"));
+        assert!(fs::read_to_string(&copia).unwrap().contains("\"Todos\",}"));
+        escribir_json(&ruta, &EquipoConfig { filtros: vec![] }).unwrap();
+        assert!(leer_json::<EquipoConfig>(&ruta).unwrap().is_some());
+    }
+
+    #[test]
+    fn arreglar_el_archivo_a_mano_lo_desbloquea() {
+        let dir = Temporal::nuevo("datos-arreglado");
+        let ruta = dir.0.join("pistas.json");
+        fs::write(&ruta, "{").unwrap();
+        assert!(leer_json::<ConfigPistas>(&ruta).is_err());
+        assert!(escribir_json(&ruta, &ConfigPistas::default()).is_err());
+
+        fs::write(&ruta, "{}").unwrap();
+        assert!(leer_json::<ConfigPistas>(&ruta).unwrap().is_some());
+        escribir_json(&ruta, &ConfigPistas::default()).unwrap();
+        assert!(leer_json::<Vec<Partido>>(&dir.0.join("no-existe.json"))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn una_escritura_cortada_deja_el_archivo_anterior() {
+        let dir = Temporal::nuevo("datos-cortada");
+        let ruta = dir.0.join("pistas.json");
+        fs::write(&ruta, "{}").unwrap();
+        // El temporal no se puede crear: como si el programa muriera antes
+        // de renombrarlo.
+        let temporal = dir
+            .0
+            .join(format!(".pistas.json.tmp-{}", std::process::id()));
+        fs::create_dir(&temporal).unwrap();
+
+        let error = escribir_json(&ruta, &vec!["nuevo"]).unwrap_err();
+        assert!(matches!(error, ErrorDatos::Escritura { .. }), "{:?}", error);
+        assert_eq!(fs::read_to_string(&ruta).unwrap(), "{}");
+
+        fs::remove_dir(&temporal).unwrap();
+        escribir_json(&ruta, &vec!["nuevo"]).unwrap();
+        assert_eq!(
+            leer_json::<Vec<String>>(&ruta).unwrap(),
+            Some(vec!["nuevo".to_string()])
+        );
+        assert!(!temporal.exists());
+    }
+
+    #[test]
+    fn las_copias_rotan_y_se_restauran() {
+        let dir = Temporal::nuevo("datos-copias");
+        let dirs = directorios_con(&[], Some(&dir.0.join("perfil").to_string_lossy()), None);
+
+        // El directorio todavía no existe: se crea al escribir.
+        let ruta = dirs.ruta("favoritos.json");
+        for version in 1..=7 {
+            escribir_json_con(&dirs, &ruta, &version).unwrap();
+        }
+        // Guardar lo mismo no gasta una copia.
+        escribir_json_con(&dirs, &ruta, &7).unwrap();
+        let copias = dirs.listar_copias("favoritos.json");
+        assert_eq!(copias.len(), COPIAS_MAXIMAS);
+        let version = |ruta: &Path| fs::read_to_string(ruta).unwrap();
+        let en_copias: Vec<String> = copias.iter().map(|(_, ruta, _)| version(ruta)).collect();
+        assert_eq!(en_copias, ["6", "5", "4", "3", "2"]);
+
+        assert_eq!(dirs.restaurar_copia("favoritos.json", 3).unwrap(), ruta);
+        assert_eq!(version(&ruta), "4");
+        assert_eq!(version(&dirs.ruta_copia("favoritos.json", 1)), "7");
+        assert_eq!(version(&dirs.ruta_copia("favoritos.json", 5)), "3");
+        assert!(dirs
+            .restaurar_copia("favoritos.json", COPIAS_MAXIMAS + 1)
+            .is_err());
+    }
+
+    /// Directorios calculados con un entorno inventado en lugar del real.
+    fn directorios_con(
+        variables: &[(&str, &str)],
+        data_dir: Option<&str>,
+        perfil: Option<&str>,
+    ) -> Directorios {
+        let variables: BTreeMap<String, OsString> = variables
+            .iter()
+            .map(|(k, v)| (k.to_string(), OsString::from(v)))
+            .collect();
+        calcular_directorios(
+            data_dir.map(PathBuf::from),
+            perfil.map(String::from),
+            &|variable| variables.get(variable).cloned(),
+            &|| PathBuf::from("/opt/fecapa"),
+        )
+    }
+
+    #[test]
+    fn los_directorios_siguen_xdg() {
+        let dirs = directorios_con(
+            &[
+                ("HOME", "/home/ana"),
+                ("XDG_DATA_HOME", "/datos"),
+                // Las rutas relativas no valen según la especificación.
+                ("XDG_CONFIG_HOME", "config"),
+            ],
+            None,
+            None,
+        );
+        assert_eq!(dirs.config, Path::new("/home/ana/.config/fecapa-explorer"));
+        assert_eq!(dirs.datos, Path::new("/datos/fecapa-explorer"));
+        assert_eq!(dirs.cache, Path::new("/home/ana/.cache/fecapa-explorer"));
+        assert_eq!(
+            dirs.ruta("teclas.json"),
+            Path::new("/home/ana/.config/fecapa-explorer/teclas.json")
+        );
+        assert_eq!(
+            dirs.ruta("actas.json"),
+            Path::new("/datos/fecapa-explorer/actas.json")
+        );
+        assert!(!dirs.forzados);
+
+        let club = directorios_con(
+            &[("HOME", "/home/ana"), ("FECAPA_PERFIL", "club")],
+            None,
+            None,
+        );
+        assert_eq!(
+            club.datos,
+            Path::new("/home/ana/.local/share/fecapa-explorer/perfiles/club")
+        );
+
+        let forzados = directorios_con(&[("HOME", "/home/ana")], Some("/tmp/fecapa"), None);
+        assert_eq!(forzados.config, Path::new("/tmp/fecapa"));
+        assert_eq!(forzados.cache, Path::new("/tmp/fecapa/cache"));
+        assert!(forzados.forzados);
+
+        // Sin HOME ni XDG, donde estaban antes.
+        let sin_home = directorios_con(&[], None, Some("club"));
+        assert_eq!(sin_home.config, Path::new("/opt/fecapa/perfiles/club"));
+    }
+
+    #[test]
+    fn la_migracion_copia_una_vez_sin_sobrescribir() {
+        let dir = Temporal::nuevo("datos-migracion");
+        let heredado = dir.0.join("antes");
+        fs::create_dir_all(heredado.join("temporadas")).unwrap();
+        fs::write(heredado.join("equipos.json"), "[]").unwrap();
+        fs::write(
+            heredado.join("favoritos.json"),
+            "{\"favoritos\": [\"viejo\"]}",
+        )
+        .unwrap();
+        fs::write(heredado.join("temporadas/2024-25.json"), "[]").unwrap();
+        let home = dir.0.join("home");
+        let dirs = directorios_con(&[("HOME", home.to_str().unwrap())], None, None);
+        fs::create_dir_all(&dirs.datos).unwrap();
+        fs::write(dirs.ruta("favoritos.json"), "{}").unwrap();
+
+        assert_eq!(dirs.migrar_desde(&heredado), ["equipos.json", "temporadas"]);
+        assert_eq!(fs::read_to_string(dirs.ruta("equipos.json")).unwrap(), "[]");
+        assert!(dirs.ruta("temporadas/2024-25.json").exists());
+        assert_eq!(
+            fs::read_to_string(dirs.ruta("favoritos.json")).unwrap(),
+            "{}"
+        );
+        assert!(heredado.join("equipos.json").exists());
+
+        // Con la marca ya no se vuelve a copiar aunque falte algo.
+        fs::remove_file(dirs.ruta("equipos.json")).unwrap();
+        assert!(dirs.migrar_desde(&heredado).is_empty());
+        let club = directorios_con(&[("HOME", home.to_str().unwrap())], None, Some("club"));
+        assert!(club.migrar_desde(&heredado).is_empty());
+    }
+
+    #[test]
+    fn los_perfiles_son_los_subdirectorios() {
+        let dir = Temporal::nuevo("datos-perfiles");
+        let dirs = directorios_con(&[], Some(dir.0.to_str().unwrap()), None);
+        assert!(dirs.perfiles().is_empty());
+        for perfil in ["juvenil", "club"] {
+            fs::create_dir_all(dirs.config.join("perfiles").join(perfil)).unwrap();
+        }
+        fs::write(dirs.config.join("perfiles/notas.txt"), "").unwrap();
+        assert_eq!(dirs.perfiles(), ["club", "juvenil"]);
+
+        let club = directorios_con(&[], Some(dir.0.to_str().unwrap()), Some("club"));
+        assert_eq!(club.perfiles(), ["club", "juvenil"]);
+    }
+}
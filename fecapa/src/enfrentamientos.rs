@@ -0,0 +1,134 @@
+use crate::models::Partido;
+
+/// Historial de partidos entre dos equipos, visto desde `equipo`.
+#[derive(Debug, Clone, Default)]
+pub struct CaraACara {
+    pub equipo: String,
+    pub rival: String,
+    pub partidos: Vec<Partido>,
+    pub ganados: usize,
+    pub empatados: usize,
+    pub perdidos: usize,
+    pub goles_favor: u32,
+    pub goles_contra: u32,
+    pub vuelta: Option<Partido>,
+}
+
+fn mismo_equipo(a: &str, b: &str) -> bool {
+    a.trim().to_uppercase() == b.trim().to_uppercase()
+}
+
+/// Enfrentamientos entre los equipos de `p` en `historial` (todas las
+/// competiciones y temporadas) y el partido de vuelta en `temporada`.
+pub fn cara_a_cara<'a>(
+    historial: impl IntoIterator<Item = &'a Partido>,
+    temporada: &[Partido],
+    p: &Partido,
+) -> CaraACara {
+    let mut resumen = CaraACara {
+        equipo: p.local.clone(),
+        rival: p.visitante.clone(),
+        ..Default::default()
+    };
+
+    resumen.partidos = historial
+        .into_iter()
+        .filter(|o| o.juega(&p.local) && o.juega(&p.visitante))
+        .cloned()
+        .collect();
+    resumen.partidos.sort_by_key(|o| o.fecha());
+
+    for o in &resumen.partidos {
+        let Some((gl, gv)) = o.goles() else {
+            continue;
+        };
+        let (favor, contra) = if mismo_equipo(&o.local, &p.local) {
+            (gl, gv)
+        } else {
+            (gv, gl)
+        };
+        resumen.goles_favor += favor;
+        resumen.goles_contra += contra;
+        match favor.cmp(&contra) {
+            std::cmp::Ordering::Greater => resumen.ganados += 1,
+            std::cmp::Ordering::Equal => resumen.empatados += 1,
+            std::cmp::Ordering::Less => resumen.perdidos += 1,
+        }
+    }
+
+    resumen.vuelta = temporada
+        .iter()
+        .find(|o| {
+            o.competicion == p.competicion
+                && mismo_equipo(&o.local, &p.visitante)
+                && mismo_equipo(&o.visitante, &p.local)
+        })
+        .cloned();
+
+    resumen
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn partido(
+        competicion: &str,
+        data: &str,
+        local: &str,
+        visitante: &str,
+        resultado: &str,
+    ) -> Partido {
+        Partido {
+            competicion: competicion.to_string(),
+            data: data.to_string(),
+            local: local.to_string(),
+            visitante: visitante.to_string(),
+            resultado: resultado.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn el_balance_es_del_equipo_local_del_partido() {
+        let historial = [
+            partido("OK LLIGA", "04/10/2025", "CP VIC", "CE NOIA", "3-1"),
+            // De visitante: 2-2 es empate y 4-0 una derrota del Vic.
+            partido("OK LLIGA", "01/02/2026", "ce noia ", "CP VIC", "2-2"),
+            partido("COPA DEL REI", "15/02/2026", "CE NOIA", "CP VIC", "4-0"),
+            partido("OK LLIGA", "11/10/2025", "CP VIC", "CH LLOBREGAT", "5-0"),
+            partido("OK LLIGA", "20/05/2026", "CP VIC", "CE NOIA", ""),
+        ];
+        let h2h = cara_a_cara(&historial, &[], &historial[0]);
+
+        assert_eq!(
+            (h2h.equipo.as_str(), h2h.rival.as_str()),
+            ("CP VIC", "CE NOIA")
+        );
+        assert_eq!(h2h.partidos.len(), 4);
+        assert_eq!((h2h.ganados, h2h.empatados, h2h.perdidos), (1, 1, 1));
+        assert_eq!((h2h.goles_favor, h2h.goles_contra), (5, 7));
+        assert_eq!(h2h.partidos[0].data, "04/10/2025");
+    }
+
+    #[test]
+    fn la_vuelta_es_de_la_misma_competicion_y_temporada() {
+        let ida = partido("OK LLIGA", "04/10/2025", "CP VIC", "CE NOIA", "3-1");
+        let anterior = partido("OK LLIGA", "01/02/2025", "CE NOIA", "CP VIC", "1-1");
+        let copa = partido("COPA DEL REI", "15/02/2026", "CE NOIA", "CP VIC", "");
+        let vuelta = partido("OK LLIGA", "01/02/2026", "CE NOIA", "CP VIC", "");
+        let historial = [anterior, ida.clone(), copa.clone(), vuelta.clone()];
+
+        let h2h = cara_a_cara(
+            &historial,
+            &[ida.clone(), copa.clone(), vuelta.clone()],
+            &ida,
+        );
+        assert_eq!(h2h.vuelta, Some(vuelta));
+        assert_eq!(h2h.partidos.len(), 4);
+        assert_eq!(
+            cara_a_cara(&historial, &[ida.clone(), copa], &ida).vuelta,
+            None
+        );
+    }
+}
@@ -0,0 +1,322 @@
+use crate::models::Partido;
+use chrono::{DateTime, Local};
+use regex::Regex;
+use serde::de::value::{MapAccessDeserializer, SeqAccessDeserializer};
+use serde::de::{Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::LazyLock;
+
+/// Versión del formato de `partidos.json` que escribe esta versión.
+pub const VERSION: u32 = 1;
+
+/// Por encima de esta proporción de filas sospechosas se considera que la
+/// tabla de la FECAPA ha cambiado y los datos no se aceptan.
+pub const PROPORCION_MAXIMA_SOSPECHOSAS: f64 = 0.1;
+
+static HORA: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\d{1,2}[:.h]\d{2}$").unwrap());
+static RESULTADO: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\d+\s*-\s*\d+$").unwrap());
+
+/// Contenido de `partidos.json`. Las versiones antiguas eran solo la lista
+/// de partidos; se leen como versión 0, sin fecha ni URL.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ArchivoPartidos {
+    pub version: u32,
+    /// Cuándo se extrajo la tabla (RFC 3339).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extraido: Option<String>,
+    /// Página de la que se extrajo.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub url: String,
+    /// Cuándo lo obtuvo esta aplicación (RFC 3339).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub descargado: Option<String>,
+    /// Fuente de `ajustes.json` que lo dio.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub fuente: String,
+    /// Número de partidos, para detectar archivos cortados.
+    pub filas: usize,
+    pub partidos: Vec<Partido>,
+}
+
+impl ArchivoPartidos {
+    /// Antigüedad de los datos: cuándo se extrajeron o, si no se sabe,
+    /// cuándo se descargaron.
+    pub fn actualizado(&self) -> Option<DateTime<Local>> {
+        [&self.extraido, &self.descargado]
+            .into_iter()
+            .flatten()
+            .find_map(|fecha| DateTime::parse_from_rfc3339(fecha).ok())
+            .map(|fecha| fecha.with_timezone(&Local))
+    }
+}
+
+#[derive(Deserialize)]
+struct Sobre {
+    version: u32,
+    #[serde(default)]
+    extraido: Option<String>,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    descargado: Option<String>,
+    #[serde(default)]
+    fuente: String,
+    filas: usize,
+    partidos: Vec<Partido>,
+}
+
+// A mano en lugar de `#[serde(untagged)]` para que los errores de formato
+// sigan indicando la línea y la columna.
+impl<'de> Deserialize<'de> for ArchivoPartidos {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Formato;
+
+        impl<'de> Visitor<'de> for Formato {
+            type Value = ArchivoPartidos;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("una lista de partidos o un objeto con \"partidos\"")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+                let partidos = Vec::<Partido>::deserialize(SeqAccessDeserializer::new(seq))?;
+                Ok(ArchivoPartidos {
+                    version: 0,
+                    filas: partidos.len(),
+                    partidos,
+                    ..Default::default()
+                })
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                let sobre = Sobre::deserialize(MapAccessDeserializer::new(map))?;
+                Ok(ArchivoPartidos {
+                    version: sobre.version,
+                    extraido: sobre.extraido,
+                    url: sobre.url,
+                    descargado: sobre.descargado,
+                    fuente: sobre.fuente,
+                    filas: sobre.filas,
+                    partidos: sobre.partidos,
+                })
+            }
+        }
+
+        deserializer.deserialize_any(Formato)
+    }
+}
+
+/// Un campo de una fila que no tiene pinta de lo que debería ser.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sospecha {
+    /// Posición en la lista, empezando por 1.
+    pub fila: usize,
+    pub campo: &'static str,
+    pub valor: String,
+    pub motivo: &'static str,
+}
+
+impl fmt::Display for Sospecha {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "fila {}: {} «{}» {}",
+            self.fila, self.campo, self.valor, self.motivo
+        )
+    }
+}
+
+fn parece_fecha(texto: &str) -> bool {
+    Partido {
+        data: texto.to_string(),
+        ..Default::default()
+    }
+    .fecha()
+    .is_some()
+}
+
+/// Lo que parece `texto` si es una fecha, hora o resultado.
+fn parece_dato(texto: &str) -> Option<&'static str> {
+    let texto = texto.trim();
+    if HORA.is_match(texto) {
+        Some("parece una hora")
+    } else if RESULTADO.is_match(texto) {
+        Some("parece un resultado")
+    } else if parece_fecha(texto) {
+        Some("parece una fecha")
+    } else {
+        None
+    }
+}
+
+/// Busca columnas desplazadas: fechas ilegibles, equipos vacíos y horas,
+/// fechas o resultados en campos de texto.
+pub fn validar(partidos: &[Partido]) -> Vec<Sospecha> {
+    let mut sospechas = Vec::new();
+    for (i, p) in partidos.iter().enumerate() {
+        let mut anotar = |campo, valor: &str, motivo| {
+            sospechas.push(Sospecha {
+                fila: i + 1,
+                campo,
+                valor: valor.to_string(),
+                motivo,
+            })
+        };
+
+        if !p.data.trim().is_empty() && p.fecha().is_none() {
+            anotar("fecha", &p.data, "no es una fecha");
+        }
+        for (campo, equipo) in [("local", &p.local), ("visitante", &p.visitante)] {
+            if equipo.trim().is_empty() {
+                anotar(campo, equipo, "está vacío");
+            }
+        }
+        for (campo, texto) in [
+            ("competición", &p.competicion),
+            ("local", &p.local),
+            ("visitante", &p.visitante),
+            ("pista", &p.pista),
+        ] {
+            if let Some(motivo) = parece_dato(texto) {
+                anotar(campo, texto, motivo);
+            }
+        }
+        if parece_fecha(p.hora.trim()) {
+            anotar("hora", &p.hora, "parece una fecha");
+        }
+        if parece_fecha(p.resultado.trim()) {
+            anotar("resultado", &p.resultado, "parece una fecha");
+        }
+    }
+    sospechas
+}
+
+/// Decide si unos partidos recién obtenidos pueden sustituir a los
+/// guardados: el formato tiene que ser conocido, estar completo, traer algún
+/// partido y no tener demasiadas filas sospechosas. Es la única comprobación:
+/// el workflow del scraper también pasa por aquí con `--validar`.
+pub fn comprobar(archivo: &ArchivoPartidos) -> Result<(), String> {
+    if archivo.version > VERSION {
+        return Err(format!(
+            "formato versión {} (esta versión lee hasta la {})",
+            archivo.version, VERSION
+        ));
+    }
+    if archivo.filas != archivo.partidos.len() {
+        return Err(format!(
+            "anuncia {} filas pero trae {}",
+            archivo.filas,
+            archivo.partidos.len()
+        ));
+    }
+    if archivo.partidos.is_empty() {
+        return Err("no trae ningún partido".to_string());
+    }
+    let sospechas = validar(&archivo.partidos);
+    let mut filas: Vec<usize> = sospechas.iter().map(|s| s.fila).collect();
+    filas.dedup();
+    if filas.len() as f64 > PROPORCION_MAXIMA_SOSPECHOSAS * archivo.partidos.len() as f64 {
+        return Err(format!(
+            "{} de {} filas sospechosas ({}); ¿ha cambiado la tabla de la FECAPA?",
+            filas.len(),
+            archivo.partidos.len(),
+            sospechas[0]
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn partido(local: &str, visitante: &str) -> Partido {
+        Partido {
+            competicion: "BCN ALEVÍ OR P4".to_string(),
+            data: "25/10/2026".to_string(),
+            hora: "10:00".to_string(),
+            local: local.to_string(),
+            visitante: visitante.to_string(),
+            resultado: "3-1".to_string(),
+            pista: "PAV. MUNICIPAL VIC".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn archivo(partidos: Vec<Partido>) -> ArchivoPartidos {
+        ArchivoPartidos {
+            version: VERSION,
+            filas: partidos.len(),
+            partidos,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn acepta_una_agenda_normal() {
+        let partidos = vec![
+            partido("CP VIC", "CP MANLLEU"),
+            partido("CE NOIA", "CH LLOBREGAT"),
+        ];
+        assert_eq!(validar(&partidos), []);
+        assert_eq!(comprobar(&archivo(partidos)), Ok(()));
+    }
+
+    #[test]
+    fn encuentra_las_columnas_desplazadas() {
+        // La hora en el local, el local en el visitante y así sucesivamente.
+        let desplazado = Partido {
+            data: "CP VIC".to_string(),
+            local: "10:00".to_string(),
+            visitante: "".to_string(),
+            pista: "3-1".to_string(),
+            ..partido("", "")
+        };
+        let campos: Vec<(&str, &str)> = validar(&[desplazado])
+            .iter()
+            .map(|s| (s.campo, s.motivo))
+            .collect();
+        assert_eq!(
+            campos,
+            [
+                ("fecha", "no es una fecha"),
+                ("visitante", "está vacío"),
+                ("local", "parece una hora"),
+                ("pista", "parece un resultado"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rechaza_si_pasa_del_limite_de_sospechosas() {
+        let mut partidos = vec![partido("CP VIC", "CP MANLLEU"); 10];
+        partidos[0].local = "25/10/2026".to_string();
+        // Una de diez está justo en el límite.
+        assert_eq!(comprobar(&archivo(partidos.clone())), Ok(()));
+
+        partidos[1].visitante = String::new();
+        let error = comprobar(&archivo(partidos)).unwrap_err();
+        assert!(
+            error.starts_with("2 de 10 filas sospechosas (fila 1: local"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn rechaza_archivos_vacios_cortados_o_de_otra_version() {
+        assert!(comprobar(&archivo(Vec::new())).is_err());
+
+        let mut cortado = archivo(vec![partido("CP VIC", "CP MANLLEU")]);
+        cortado.filas = 2;
+        assert_eq!(
+            comprobar(&cortado),
+            Err("anuncia 2 filas pero trae 1".to_string())
+        );
+
+        let mut futuro = archivo(vec![partido("CP VIC", "CP MANLLEU")]);
+        futuro.version = VERSION + 1;
+        assert!(comprobar(&futuro).is_err());
+    }
+}
@@ -0,0 +1,185 @@
+use crate::models::Partido;
+use std::collections::HashMap;
+
+pub const PUNTOS_VICTORIA: u32 = 3;
+pub const PUNTOS_EMPATE: u32 = 1;
+
+#[derive(Debug, Clone, Default)]
+pub struct Balance {
+    pub jugados: u32,
+    pub ganados: u32,
+    pub empatados: u32,
+    pub perdidos: u32,
+    pub goles_favor: u32,
+    pub goles_contra: u32,
+}
+
+impl Balance {
+    pub fn sumar(&mut self, favor: u32, contra: u32) {
+        self.jugados += 1;
+        self.goles_favor += favor;
+        self.goles_contra += contra;
+        match favor.cmp(&contra) {
+            std::cmp::Ordering::Greater => self.ganados += 1,
+            std::cmp::Ordering::Equal => self.empatados += 1,
+            std::cmp::Ordering::Less => self.perdidos += 1,
+        }
+    }
+
+    pub fn puntos(&self) -> u32 {
+        self.ganados * PUNTOS_VICTORIA + self.empatados * PUNTOS_EMPATE
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Linea {
+    pub equipo: String,
+    pub puntos: Vec<(f64, f64)>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Estadisticas {
+    pub equipo: String,
+    pub competicion: String,
+    pub goles_favor: Vec<(f64, f64)>,
+    pub goles_contra: Vec<(f64, f64)>,
+    pub puntos: Vec<Linea>,
+    pub casa: Balance,
+    pub fuera: Balance,
+}
+
+impl Estadisticas {
+    pub fn total(&self) -> Balance {
+        Balance {
+            jugados: self.casa.jugados + self.fuera.jugados,
+            ganados: self.casa.ganados + self.fuera.ganados,
+            empatados: self.casa.empatados + self.fuera.empatados,
+            perdidos: self.casa.perdidos + self.fuera.perdidos,
+            goles_favor: self.casa.goles_favor + self.fuera.goles_favor,
+            goles_contra: self.casa.goles_contra + self.fuera.goles_contra,
+        }
+    }
+}
+
+fn mismo_equipo(a: &str, b: &str) -> bool {
+    a.trim().to_uppercase() == b.trim().to_uppercase()
+}
+
+/// Nombre exacto del equipo al que apunta el texto de búsqueda de un filtro:
+/// el que más veces aparece entre los partidos filtrados.
+pub fn equipo_del_filtro(partidos: &[Partido], buscar: &str) -> Option<String> {
+    let buscar = buscar.trim().to_uppercase();
+    if buscar.is_empty() {
+        return None;
+    }
+    let mut apariciones: HashMap<&str, usize> = HashMap::new();
+    for p in partidos {
+        for equipo in [&p.local, &p.visitante] {
+            if equipo.to_uppercase().contains(&buscar) {
+                *apariciones.entry(equipo.trim()).or_default() += 1;
+            }
+        }
+    }
+    apariciones
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(a.0)))
+        .map(|(equipo, _)| equipo.to_string())
+}
+
+/// Partidos jugados ordenados por fecha, con los goles ya interpretados.
+pub fn jugados_por_fecha<'a>(
+    partidos: impl Iterator<Item = &'a Partido>,
+) -> Vec<(&'a Partido, u32, u32)> {
+    let mut jugados: Vec<(&Partido, u32, u32)> = partidos
+        .filter_map(|p| p.goles().map(|(l, v)| (p, l, v)))
+        .collect();
+    jugados.sort_by_key(|(p, _, _)| (p.fecha(), p.hora.clone()));
+    jugados
+}
+
+/// Puntos acumulados de cada equipo de una competición, con la fecha del
+/// partido expresada en días desde el primero jugado.
+fn puntos_acumulados(todos: &[Partido], competicion: &str) -> HashMap<String, Linea> {
+    let jugados = jugados_por_fecha(todos.iter().filter(|p| p.competicion == competicion));
+    let inicio = jugados.iter().find_map(|(p, _, _)| p.fecha());
+    let mut lineas: HashMap<String, Linea> = HashMap::new();
+
+    for (p, l, v) in jugados {
+        let (Some(fecha), Some(inicio)) = (p.fecha(), inicio) else {
+            continue;
+        };
+        let dia = (fecha - inicio).num_days() as f64;
+        for (equipo, favor, contra) in [(&p.local, l, v), (&p.visitante, v, l)] {
+            let linea = lineas
+                .entry(equipo.trim().to_uppercase())
+                .or_insert_with(|| Linea {
+                    equipo: equipo.trim().to_string(),
+                    puntos: vec![(0.0, 0.0)],
+                });
+            let anteriores = linea.puntos.last().map(|(_, y)| *y).unwrap_or(0.0);
+            let ganados = match favor.cmp(&contra) {
+                std::cmp::Ordering::Greater => PUNTOS_VICTORIA,
+                std::cmp::Ordering::Equal => PUNTOS_EMPATE,
+                std::cmp::Ordering::Less => 0,
+            };
+            linea.puntos.push((dia, anteriores + ganados as f64));
+        }
+    }
+    lineas
+}
+
+/// Estadísticas del equipo en los partidos del filtro activo, comparando sus
+/// puntos con los `rivales` mejor clasificados de su competición principal.
+pub fn calcular(
+    partidos: &[Partido],
+    todos: &[Partido],
+    equipo: &str,
+    rivales: usize,
+) -> Estadisticas {
+    let propios: Vec<&Partido> = partidos.iter().filter(|p| p.juega(equipo)).collect();
+    let mut stats = Estadisticas {
+        equipo: equipo.to_string(),
+        ..Default::default()
+    };
+
+    for (i, (p, l, v)) in jugados_por_fecha(propios.iter().copied())
+        .into_iter()
+        .enumerate()
+    {
+        let en_casa = mismo_equipo(&p.local, equipo);
+        let (favor, contra) = if en_casa { (l, v) } else { (v, l) };
+        let x = (i + 1) as f64;
+        stats.goles_favor.push((x, favor as f64));
+        stats.goles_contra.push((x, contra as f64));
+        if en_casa {
+            stats.casa.sumar(favor, contra);
+        } else {
+            stats.fuera.sumar(favor, contra);
+        }
+    }
+
+    let mut frecuencia: HashMap<&str, usize> = HashMap::new();
+    for p in &propios {
+        *frecuencia.entry(p.competicion.as_str()).or_default() += 1;
+    }
+    let Some((competicion, _)) = frecuencia
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(a.0)))
+    else {
+        return stats;
+    };
+    stats.competicion = competicion.to_string();
+
+    let mut lineas = puntos_acumulados(todos, competicion);
+    if let Some(propia) = lineas.remove(&equipo.trim().to_uppercase()) {
+        stats.puntos.push(propia);
+    }
+    let mut otras: Vec<Linea> = lineas.into_values().collect();
+    otras.sort_by(|a, b| {
+        let fa = a.puntos.last().map(|(_, y)| *y).unwrap_or(0.0);
+        let fb = b.puntos.last().map(|(_, y)| *y).unwrap_or(0.0);
+        fb.total_cmp(&fa).then(a.equipo.cmp(&b.equipo))
+    });
+    stats.puntos.extend(otras.into_iter().take(rivales));
+    stats
+}
@@ -0,0 +1,698 @@
+use crate::agenda::{parsear_agenda, URL_AGENDA};
+use crate::datos::{buscar_script, escribir_atomico, get_cache_dir, is_android};
+use crate::esquema::{self, ArchivoPartidos};
+use crate::models::Partido;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub const URL_GITHUB: &str =
+    "https://raw.githubusercontent.com/tortajet/fecapa-explorer/main/partidos.json";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "tipo", rename_all = "lowercase")]
+pub enum TipoFuente {
+    /// La agenda de la FECAPA leída directamente, sin Node ni navegador.
+    Nativo {
+        #[serde(default = "url_agenda")]
+        url: String,
+    },
+    /// Un `partidos.json` publicado en HTTP (GitHub, el mirror de un club...).
+    Url { url: String },
+    /// Un `partidos.json` en disco.
+    Archivo { ruta: String },
+    /// Un programa que genera los partidos: en el archivo `salida` si se
+    /// indica o, si no, por la salida estándar. Se ejecuta en un directorio
+    /// de trabajo aparte, así que una `salida` relativa nunca pisa los
+    /// archivos de datos.
+    Comando {
+        comando: String,
+        #[serde(default)]
+        args: Vec<String>,
+        #[serde(default)]
+        salida: Option<String>,
+    },
+}
+
+fn url_agenda() -> String {
+    URL_AGENDA.to_string()
+}
+
+fn timeout_por_defecto() -> u64 {
+    30
+}
+
+/// Una fuente tal como se configura en `ajustes.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fuente {
+    #[serde(default)]
+    pub nombre: String,
+    #[serde(flatten)]
+    pub tipo: TipoFuente,
+    /// Las fuentes se prueban de menor a mayor prioridad.
+    #[serde(default)]
+    pub prioridad: i32,
+    #[serde(default = "timeout_por_defecto")]
+    pub timeout_segundos: u64,
+}
+
+impl Fuente {
+    pub fn describir(&self) -> String {
+        if !self.nombre.is_empty() {
+            return self.nombre.clone();
+        }
+        match &self.tipo {
+            TipoFuente::Nativo { url } | TipoFuente::Url { url } => url.clone(),
+            TipoFuente::Archivo { ruta } => ruta.clone(),
+            TipoFuente::Comando { comando, args, .. } => {
+                format!("{} {}", comando, args.join(" ")).trim().to_string()
+            }
+        }
+    }
+
+    /// La implementación que corresponde a esta configuración.
+    pub fn crear(&self, proxy: Option<&str>) -> Box<dyn FuenteDatos> {
+        let nombre = self.describir();
+        let timeout = Duration::from_secs(self.timeout_segundos);
+        let proxy = proxy.map(str::to_string);
+        match &self.tipo {
+            TipoFuente::Nativo { url } => Box::new(ScraperNativo {
+                nombre,
+                url: url.clone(),
+                timeout,
+                proxy,
+            }),
+            TipoFuente::Url { url } => Box::new(EspejoHttp {
+                nombre,
+                url: url.clone(),
+                timeout,
+                proxy,
+            }),
+            TipoFuente::Archivo { ruta } => Box::new(ArchivoLocal {
+                nombre,
+                ruta: PathBuf::from(ruta),
+            }),
+            TipoFuente::Comando {
+                comando,
+                args,
+                salida,
+            } => Box::new(Programa {
+                nombre,
+                comando: comando.clone(),
+                args: args.clone(),
+                salida: salida.clone(),
+                timeout,
+            }),
+        }
+    }
+}
+
+/// Sin configuración se comporta como siempre: el scraper con Node y, si
+/// falla o estamos en Android (donde no se lanzan programas), la copia que
+/// publica el workflow en GitHub. El scraper escribe los partidos por la
+/// salida estándar: `partidos.json` solo lo escribe `guardar_partidos`,
+/// después de validarlos y con la copia de la versión anterior.
+pub fn fuentes_por_defecto() -> Vec<Fuente> {
+    let github = Fuente {
+        nombre: "GitHub".to_string(),
+        tipo: TipoFuente::Url {
+            url: URL_GITHUB.to_string(),
+        },
+        prioridad: 1,
+        timeout_segundos: 30,
+    };
+    vec![
+        Fuente {
+            nombre: "Scraper".to_string(),
+            tipo: TipoFuente::Comando {
+                comando: "node".to_string(),
+                args: vec![buscar_script("scrape-hockey.js").display().to_string()],
+                salida: None,
+            },
+            prioridad: 0,
+            timeout_segundos: 120,
+        },
+        github,
+    ]
+}
+
+/// Contenido de `ajustes.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ajustes {
+    #[serde(default = "fuentes_por_defecto")]
+    pub fuentes: Vec<Fuente>,
+    /// Proxy para las fuentes HTTP, p. ej. "http://proxy:3128". Sin él se
+    /// respetan HTTP_PROXY/HTTPS_PROXY.
+    #[serde(default)]
+    pub proxy: Option<String>,
+    /// A partir de cuántas horas se avisa de que los partidos son viejos.
+    #[serde(default = "horas_caducidad_por_defecto")]
+    pub horas_caducidad: i64,
+    /// Refrescar al arrancar si los partidos son más viejos que eso.
+    #[serde(default)]
+    pub refrescar_al_iniciar: bool,
+}
+
+fn horas_caducidad_por_defecto() -> i64 {
+    24
+}
+
+impl Default for Ajustes {
+    fn default() -> Self {
+        Self {
+            fuentes: fuentes_por_defecto(),
+            proxy: None,
+            horas_caducidad: horas_caducidad_por_defecto(),
+            refrescar_al_iniciar: false,
+        }
+    }
+}
+
+/// Validadores HTTP de la última respuesta de una URL.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct EntradaCache {
+    #[serde(default)]
+    etag: Option<String>,
+    #[serde(default)]
+    modificado: Option<String>,
+}
+
+fn ruta_cache(cache: &Path, url: &str) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    url.hash(&mut hasher);
+    cache.join(format!("{:016x}", hasher.finish()))
+}
+
+fn leer_cache(cache: &Path, url: &str) -> Option<(EntradaCache, String)> {
+    let ruta = ruta_cache(cache, url);
+    let meta = fs::read_to_string(ruta.with_extension("meta.json")).ok()?;
+    let cuerpo = fs::read_to_string(ruta.with_extension("json")).ok()?;
+    Some((serde_json::from_str(&meta).ok()?, cuerpo))
+}
+
+/// Guarda primero el cuerpo y después los validadores, los dos de forma
+/// atómica: un `.meta.json` nunca apunta a un cuerpo de otra versión. Si
+/// los validadores no se pueden escribir se borran los viejos.
+fn guardar_cache(
+    cache: &Path,
+    url: &str,
+    entrada: &EntradaCache,
+    cuerpo: &str,
+) -> std::io::Result<()> {
+    let ruta = ruta_cache(cache, url);
+    let meta = ruta.with_extension("meta.json");
+    fs::create_dir_all(cache)?;
+    let json = serde_json::to_string_pretty(entrada)?;
+    let resultado = escribir_atomico(&ruta.with_extension("json"), cuerpo.as_bytes())
+        .and_then(|()| escribir_atomico(&meta, json.as_bytes()));
+    if resultado.is_err() {
+        let _ = fs::remove_file(&meta);
+    }
+    resultado
+}
+
+/// Descarga con If-None-Match/If-Modified-Since: si el servidor responde
+/// 304 se reutiliza la copia en caché.
+fn descargar(url: &str, timeout: Duration, proxy: Option<&str>) -> Result<String, String> {
+    descargar_con(&get_cache_dir(), url, timeout, proxy)
+}
+
+fn descargar_con(
+    dir_cache: &Path,
+    url: &str,
+    timeout: Duration,
+    proxy: Option<&str>,
+) -> Result<String, String> {
+    let mut builder = reqwest::blocking::Client::builder().timeout(timeout);
+    if let Some(proxy) = proxy {
+        builder = builder.proxy(reqwest::Proxy::all(proxy).map_err(|e| e.to_string())?);
+    }
+    let client = builder.build().map_err(|e| e.to_string())?;
+
+    let cache = leer_cache(dir_cache, url);
+    let mut request = client.get(url);
+    if let Some((entrada, _)) = &cache {
+        if let Some(etag) = &entrada.etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(modificado) = &entrada.modificado {
+            request = request.header(reqwest::header::IF_MODIFIED_SINCE, modificado);
+        }
+    }
+
+    let response = request.send().map_err(|e| e.to_string())?;
+
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        if let Some((_, cuerpo)) = cache {
+            return Ok(cuerpo);
+        }
+    }
+    if !response.status().is_success() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    let cabecera = |nombre| {
+        response
+            .headers()
+            .get(nombre)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    let entrada = EntradaCache {
+        etag: cabecera(reqwest::header::ETAG),
+        modificado: cabecera(reqwest::header::LAST_MODIFIED),
+    };
+    let cuerpo = response.text().map_err(|e| e.to_string())?;
+    if entrada.etag.is_some() || entrada.modificado.is_some() {
+        // La caché solo ahorra descargas: si no se puede guardar, lo
+        // descargado sirve igual y la próxima vez se pide entero.
+        let _ = guardar_cache(dir_cache, url, &entrada, &cuerpo);
+    }
+    Ok(cuerpo)
+}
+
+fn ejecutar(
+    comando: &str,
+    args: &[String],
+    salida: Option<&str>,
+    timeout: Duration,
+) -> Result<String, String> {
+    let dir = get_cache_dir().join("fuentes");
+    fs::create_dir_all(&dir).map_err(|e| format!("Error creando {}: {}", dir.display(), e))?;
+    let mut hijo = Command::new(comando)
+        .args(args)
+        .current_dir(&dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("No se pudo ejecutar {}: {}", comando, e))?;
+
+    // Leer en paralelo para que el proceso no se bloquee con la tubería llena.
+    let mut stdout = hijo.stdout.take();
+    let mut stderr = hijo.stderr.take();
+    let lector_stdout = thread::spawn(move || {
+        let mut texto = String::new();
+        if let Some(s) = stdout.as_mut() {
+            let _ = s.read_to_string(&mut texto);
+        }
+        texto
+    });
+    let lector_stderr = thread::spawn(move || {
+        let mut texto = String::new();
+        if let Some(s) = stderr.as_mut() {
+            let _ = s.read_to_string(&mut texto);
+        }
+        texto
+    });
+
+    let inicio = Instant::now();
+    let estado = loop {
+        if let Some(estado) = hijo.try_wait().map_err(|e| e.to_string())? {
+            break estado;
+        }
+        if inicio.elapsed() > timeout {
+            let _ = hijo.kill();
+            let _ = hijo.wait();
+            return Err(format!("{} no terminó en {}s", comando, timeout.as_secs()));
+        }
+        thread::sleep(Duration::from_millis(200));
+    };
+
+    let texto = lector_stdout.join().unwrap_or_default();
+    if !estado.success() {
+        let error = lector_stderr.join().unwrap_or_default();
+        return Err(format!("Error ejecutando {}: {}", comando, error.trim()));
+    }
+
+    match salida {
+        Some(archivo) => fs::read_to_string(dir.join(archivo))
+            .map_err(|e| format!("Error leyendo {}: {}", archivo, e)),
+        None => Ok(texto),
+    }
+}
+
+/// Lo que hace falta para usar una fuente y cómo se comporta.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Capacidades {
+    /// Necesita conexión.
+    pub red: bool,
+    /// Lanza un programa externo, cosa que en Android no se puede.
+    pub programa_externo: bool,
+    /// Pregunta con ETag/Last-Modified y no vuelve a descargar lo que no ha
+    /// cambiado.
+    pub cache_http: bool,
+}
+
+/// Una forma de conseguir los partidos. [`obtener_de`] prueba varias en
+/// orden y se encarga de validar lo que devuelven.
+pub trait FuenteDatos {
+    /// Los partidos tal como los da la fuente, sin validar.
+    fn obtener(&self) -> Result<ArchivoPartidos, String>;
+    /// Nombre para mensajes y errores.
+    fn describir(&self) -> String;
+    fn capacidades(&self) -> Capacidades;
+}
+
+fn leer_partidos(datos: &str) -> Result<ArchivoPartidos, String> {
+    serde_json::from_str(datos).map_err(|e| format!("Error parseando JSON: {}", e))
+}
+
+/// Lee la tabla de la agenda de la FECAPA con [`parsear_agenda`]. Solo
+/// funciona si la página trae la tabla en el HTML; si la monta con
+/// JavaScript hace falta el scraper de Node.
+pub struct ScraperNativo {
+    pub nombre: String,
+    pub url: String,
+    pub timeout: Duration,
+    pub proxy: Option<String>,
+}
+
+impl FuenteDatos for ScraperNativo {
+    fn obtener(&self) -> Result<ArchivoPartidos, String> {
+        let html = descargar(&self.url, self.timeout, self.proxy.as_deref())?;
+        let partidos = parsear_agenda(&html, &self.url);
+        Ok(ArchivoPartidos {
+            version: esquema::VERSION,
+            extraido: Some(Local::now().to_rfc3339()),
+            url: self.url.clone(),
+            filas: partidos.len(),
+            partidos,
+            ..Default::default()
+        })
+    }
+
+    fn describir(&self) -> String {
+        self.nombre.clone()
+    }
+
+    fn capacidades(&self) -> Capacidades {
+        Capacidades {
+            red: true,
+            cache_http: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// Un programa que deja los partidos en `salida` o los escribe por la
+/// salida estándar, normalmente `node scrape-hockey.js`.
+pub struct Programa {
+    pub nombre: String,
+    pub comando: String,
+    pub args: Vec<String>,
+    pub salida: Option<String>,
+    pub timeout: Duration,
+}
+
+impl FuenteDatos for Programa {
+    fn obtener(&self) -> Result<ArchivoPartidos, String> {
+        leer_partidos(&ejecutar(
+            &self.comando,
+            &self.args,
+            self.salida.as_deref(),
+            self.timeout,
+        )?)
+    }
+
+    fn describir(&self) -> String {
+        self.nombre.clone()
+    }
+
+    fn capacidades(&self) -> Capacidades {
+        Capacidades {
+            red: true,
+            programa_externo: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// Un `partidos.json` publicado en HTTP.
+pub struct EspejoHttp {
+    pub nombre: String,
+    pub url: String,
+    pub timeout: Duration,
+    pub proxy: Option<String>,
+}
+
+impl FuenteDatos for EspejoHttp {
+    fn obtener(&self) -> Result<ArchivoPartidos, String> {
+        leer_partidos(&descargar(&self.url, self.timeout, self.proxy.as_deref())?)
+    }
+
+    fn describir(&self) -> String {
+        self.nombre.clone()
+    }
+
+    fn capacidades(&self) -> Capacidades {
+        Capacidades {
+            red: true,
+            cache_http: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// Un `partidos.json` en disco.
+pub struct ArchivoLocal {
+    pub nombre: String,
+    pub ruta: PathBuf,
+}
+
+impl FuenteDatos for ArchivoLocal {
+    fn obtener(&self) -> Result<ArchivoPartidos, String> {
+        let datos = fs::read_to_string(&self.ruta)
+            .map_err(|e| format!("Error leyendo {}: {}", self.ruta.display(), e))?;
+        leer_partidos(&datos)
+    }
+
+    fn describir(&self) -> String {
+        self.nombre.clone()
+    }
+
+    fn capacidades(&self) -> Capacidades {
+        Capacidades::default()
+    }
+}
+
+/// Devuelve siempre el mismo resultado, para probar la cadena de fuentes
+/// sin red.
+pub struct FuenteFalsa {
+    pub nombre: String,
+    pub resultado: Result<Vec<Partido>, String>,
+    pub capacidades: Capacidades,
+}
+
+impl FuenteDatos for FuenteFalsa {
+    fn obtener(&self) -> Result<ArchivoPartidos, String> {
+        let partidos = self.resultado.clone()?;
+        Ok(ArchivoPartidos {
+            version: esquema::VERSION,
+            filas: partidos.len(),
+            partidos,
+            ..Default::default()
+        })
+    }
+
+    fn describir(&self) -> String {
+        self.nombre.clone()
+    }
+
+    fn capacidades(&self) -> Capacidades {
+        self.capacidades
+    }
+}
+
+fn validado(fuente: &dyn FuenteDatos) -> Result<ArchivoPartidos, String> {
+    if fuente.capacidades().programa_externo && is_android() {
+        return Err("en Android no se pueden lanzar programas".to_string());
+    }
+    let mut archivo = fuente.obtener()?;
+    esquema::comprobar(&archivo)?;
+    // Se guarda siempre en el formato actual aunque la fuente use uno antiguo.
+    archivo.version = esquema::VERSION;
+    Ok(archivo)
+}
+
+/// Partidos obtenidos, con la fuente y la hora de descarga anotadas.
+pub type Obtenidos = Result<ArchivoPartidos, String>;
+
+/// Prueba las fuentes en orden y devuelve los partidos de la primera que
+/// responda con datos válidos. Si fallan todas, el error junta el de cada
+/// una.
+pub fn obtener_de(fuentes: &[Box<dyn FuenteDatos>]) -> Obtenidos {
+    let mut errores = Vec::new();
+    for fuente in fuentes {
+        match validado(fuente.as_ref()) {
+            Ok(mut archivo) => {
+                archivo.descargado = Some(Local::now().to_rfc3339());
+                archivo.fuente = fuente.describir();
+                return Ok(archivo);
+            }
+            Err(e) => errores.push(format!("{}: {}", fuente.describir(), e)),
+        }
+    }
+    if errores.is_empty() {
+        return Err("No hay fuentes configuradas en ajustes.json".to_string());
+    }
+    Err(errores.join(" | "))
+}
+
+/// Las fuentes de `ajustes.json` por orden de prioridad.
+pub fn obtener(ajustes: &Ajustes) -> Obtenidos {
+    let mut fuentes: Vec<&Fuente> = ajustes.fuentes.iter().collect();
+    fuentes.sort_by_key(|f| f.prioridad);
+    let fuentes: Vec<Box<dyn FuenteDatos>> = fuentes
+        .into_iter()
+        .map(|f| f.crear(ajustes.proxy.as_deref()))
+        .collect();
+    obtener_de(&fuentes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn falsa(nombre: &str, resultado: Result<Vec<Partido>, String>) -> Box<dyn FuenteDatos> {
+        Box::new(FuenteFalsa {
+            nombre: nombre.to_string(),
+            resultado,
+            capacidades: Capacidades::default(),
+        })
+    }
+
+    fn partidos() -> Vec<Partido> {
+        vec![Partido {
+            competicion: "BCN BENJAMÍ OR P4".to_string(),
+            data: "25/10/2026".to_string(),
+            hora: "10:00".to_string(),
+            local: "CH SANT CELONI".to_string(),
+            visitante: "CP VIC".to_string(),
+            pista: "PAV. MUNICIPAL SANT CELONI".to_string(),
+            ..Default::default()
+        }]
+    }
+
+    #[test]
+    fn pasa_a_la_siguiente_si_una_falla() {
+        let fuentes = [
+            falsa("Scraper", Err("node no encontrado".to_string())),
+            falsa("Vacía", Ok(Vec::new())),
+            falsa("GitHub", Ok(partidos())),
+        ];
+        let archivo = obtener_de(&fuentes).unwrap();
+
+        assert_eq!(archivo.fuente, "GitHub");
+        assert_eq!(archivo.partidos, partidos());
+        assert!(archivo.descargado.is_some());
+    }
+
+    #[test]
+    fn rechaza_columnas_desplazadas() {
+        let desplazados: Vec<Partido> = partidos()
+            .into_iter()
+            .map(|p| Partido {
+                local: p.hora.clone(),
+                ..p
+            })
+            .collect();
+        let fuentes = [falsa("Scraper", Ok(desplazados))];
+        let error = obtener_de(&fuentes).unwrap_err();
+
+        assert!(
+            error.starts_with("Scraper: 1 de 1 filas sospechosas"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn junta_los_errores_de_todas() {
+        let fuentes = [
+            falsa("Scraper", Err("timeout".to_string())),
+            falsa("GitHub", Err("HTTP error: 404".to_string())),
+        ];
+
+        assert_eq!(
+            obtener_de(&fuentes).unwrap_err(),
+            "Scraper: timeout | GitHub: HTTP error: 404"
+        );
+        assert!(obtener_de(&[]).is_err());
+    }
+
+    /// Servidor HTTP de un solo uso en localhost: contesta cada conexión con
+    /// la respuesta que toca y devuelve las peticiones recibidas.
+    fn servidor(respuestas: Vec<&'static str>) -> (String, thread::JoinHandle<Vec<String>>) {
+        use std::io::{BufRead, BufReader, Write};
+        use std::net::TcpListener;
+
+        let escucha = TcpListener::bind("127.0.0.1:0").unwrap();
+        let direccion = format!("http://{}", escucha.local_addr().unwrap());
+        let hilo = thread::spawn(move || {
+            let mut peticiones = Vec::new();
+            for respuesta in respuestas {
+                let (mut conexion, _) = escucha.accept().unwrap();
+                let mut lector = BufReader::new(conexion.try_clone().unwrap());
+                let mut peticion = String::new();
+                while lector.read_line(&mut peticion).unwrap_or(0) > 2 {}
+                peticiones.push(peticion);
+                if respuesta.is_empty() {
+                    // Sin respuesta: que el cliente agote el timeout.
+                    thread::sleep(Duration::from_millis(500));
+                } else {
+                    conexion.write_all(respuesta.as_bytes()).unwrap();
+                }
+            }
+            peticiones
+        });
+        (direccion, hilo)
+    }
+
+    #[test]
+    fn reutiliza_la_cache_con_304() {
+        let cache = crate::pruebas::Temporal::nuevo("fuentes-304");
+        let (direccion, hilo) = servidor(vec![
+            "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\n\
+             Last-Modified: Sun, 18 Oct 2026 10:00:00 GMT\r\n\
+             Content-Length: 2\r\nConnection: close\r\n\r\n[]",
+            "HTTP/1.1 304 Not Modified\r\nConnection: close\r\n\r\n",
+        ]);
+        let url = format!("{}/partidos.json", direccion);
+        let timeout = Duration::from_secs(5);
+
+        assert_eq!(descargar_con(&cache.0, &url, timeout, None).unwrap(), "[]");
+        assert_eq!(descargar_con(&cache.0, &url, timeout, None).unwrap(), "[]");
+
+        let peticiones = hilo.join().unwrap();
+        let segunda = peticiones[1].to_lowercase();
+        assert!(!peticiones[0].to_lowercase().contains("if-none-match"));
+        assert!(segunda.contains("if-none-match: \"v1\""));
+        assert!(segunda.contains("if-modified-since: sun, 18 oct 2026 10:00:00 gmt"));
+    }
+
+    #[test]
+    fn corta_por_timeout_y_pasa_por_el_proxy() {
+        let cache = crate::pruebas::Temporal::nuevo("fuentes-proxy");
+        let (proxy, hilo) = servidor(vec![
+            "",
+            "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\n[]",
+        ]);
+        let url = "http://fecapa.invalid/partidos.json";
+
+        assert!(descargar_con(&cache.0, url, Duration::from_millis(200), Some(&proxy)).is_err());
+        let cuerpo = descargar_con(&cache.0, url, Duration::from_secs(5), Some(&proxy));
+        assert_eq!(cuerpo.unwrap(), "[]");
+
+        let peticiones = hilo.join().unwrap();
+        assert!(peticiones[1].starts_with("GET http://fecapa.invalid/partidos.json "));
+        // Sin validadores no se guarda nada en caché.
+        assert_eq!(fs::read_dir(&cache.0).unwrap().count(), 0);
+    }
+}
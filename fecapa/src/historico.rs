@@ -0,0 +1,304 @@
+use crate::esquema::ArchivoPartidos;
+use crate::models::{CambioFecha, HistoriaPartido, Partido};
+use chrono::{Local, NaiveDate, TimeZone};
+use git2::{Repository, Sort};
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Orden de los partidos con la misma clave: por fecha y hora, los que no
+/// tienen fecha al final.
+fn orden(p: &Partido) -> (bool, Option<NaiveDate>, String) {
+    let fecha = p.fecha();
+    (fecha.is_none(), fecha, p.hora.trim().to_string())
+}
+
+/// Clave de cada partido en el histórico: la de `Partido::clave_temporada`,
+/// que no cambia con los aplazamientos. Cuando los mismos equipos se cruzan
+/// con el mismo local más de una vez en la competición y temporada
+/// (eliminatorias), el segundo por fecha lleva `#2`, el tercero `#3`...
+pub fn claves(partidos: &[Partido]) -> Vec<String> {
+    let mut grupos: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, p) in partidos.iter().enumerate() {
+        grupos.entry(p.clave_temporada()).or_default().push(i);
+    }
+    let mut claves = vec![String::new(); partidos.len()];
+    for (clave, mut indices) in grupos {
+        indices.sort_by_key(|&i| orden(&partidos[i]));
+        for (n, i) in indices.into_iter().enumerate() {
+            claves[i] = match n {
+                0 => clave.clone(),
+                n => format!("{}#{}", clave, n + 1),
+            };
+        }
+    }
+    claves
+}
+
+/// Clave de `p` en el histórico, entre los partidos de su temporada.
+pub fn clave(partidos: &[Partido], p: &Partido) -> String {
+    let clave = p.clave_temporada();
+    let anteriores = partidos
+        .iter()
+        .filter(|o| orden(o) < orden(p) && o.clave_temporada() == clave)
+        .count();
+    match anteriores {
+        0 => clave,
+        n => format!("{}#{}", clave, n + 1),
+    }
+}
+
+/// Registra una versión de la agenda vista en el instante `visto`. Las
+/// versiones deben llegar en orden cronológico.
+pub fn observar(
+    historico: &mut BTreeMap<String, HistoriaPartido>,
+    partidos: &[Partido],
+    visto: i64,
+) {
+    for (p, clave) in partidos.iter().zip(claves(partidos)) {
+        let historia = historico.entry(clave).or_insert_with(|| HistoriaPartido {
+            primera_vez: visto,
+            ..Default::default()
+        });
+        historia.ultima_vez = visto;
+
+        let cambiada = historia
+            .fechas
+            .last()
+            .is_none_or(|f| f.data.trim() != p.data.trim() || f.hora.trim() != p.hora.trim());
+        if cambiada {
+            historia.fechas.push(CambioFecha {
+                visto,
+                data: p.data.trim().to_string(),
+                hora: p.hora.trim().to_string(),
+            });
+        }
+
+        if p.goles().is_some() && historia.resultado != p.resultado.trim() {
+            if historia.resultado.is_empty() {
+                historia.resultado_publicado = Some(visto);
+            }
+            historia.resultado = p.resultado.trim().to_string();
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Importacion {
+    pub commits: usize,
+    pub versiones: usize,
+    pub ilegibles: usize,
+}
+
+/// Recorre, del más antiguo al más reciente, los commits de un clon local
+/// que modificaron `archivo` y reconstruye el histórico con la hora de cada
+/// commit. No usa la red.
+pub fn importar_git(
+    repo: &Path,
+    archivo: &str,
+) -> Result<(BTreeMap<String, HistoriaPartido>, Importacion), String> {
+    let repo = Repository::discover(repo)
+        .map_err(|e| format!("No es un repositorio git: {}", e.message()))?;
+    let mut revwalk = repo
+        .revwalk()
+        .map_err(|e| format!("Error leyendo el historial: {}", e.message()))?;
+    revwalk
+        .push_head()
+        .map_err(|e| format!("Repositorio sin commits: {}", e.message()))?;
+    revwalk
+        .set_sorting(Sort::TOPOLOGICAL | Sort::TIME | Sort::REVERSE)
+        .map_err(|e| e.message().to_string())?;
+
+    let mut historico = BTreeMap::new();
+    let mut informe = Importacion::default();
+    let mut ultimo_blob = None;
+
+    for oid in revwalk {
+        let commit = oid
+            .and_then(|oid| repo.find_commit(oid))
+            .map_err(|e| format!("Error leyendo un commit: {}", e.message()))?;
+        informe.commits += 1;
+
+        let Ok(entrada) = commit.tree().and_then(|t| t.get_path(Path::new(archivo))) else {
+            continue;
+        };
+        if ultimo_blob == Some(entrada.id()) {
+            continue;
+        }
+        ultimo_blob = Some(entrada.id());
+
+        let Ok(blob) = repo.find_blob(entrada.id()) else {
+            continue;
+        };
+        match serde_json::from_slice::<ArchivoPartidos>(blob.content()) {
+            Ok(version) => {
+                informe.versiones += 1;
+                observar(&mut historico, &version.partidos, commit.time().seconds());
+            }
+            Err(_) => informe.ilegibles += 1,
+        }
+    }
+
+    if informe.versiones == 0 {
+        return Err(format!("Ningún commit contiene {}", archivo));
+    }
+    Ok((historico, informe))
+}
+
+/// Junta un histórico importado con el guardado sin perder lo que ya se
+/// sabía: de cada partido se queda la primera vez que se vio, la primera
+/// publicación del resultado y todos los horarios por orden.
+pub fn fusionar(
+    historico: &mut BTreeMap<String, HistoriaPartido>,
+    importado: BTreeMap<String, HistoriaPartido>,
+) {
+    for (clave, nueva) in importado {
+        let actual = match historico.entry(clave) {
+            Entry::Vacant(hueco) => {
+                hueco.insert(nueva);
+                continue;
+            }
+            Entry::Occupied(actual) => actual.into_mut(),
+        };
+        let nueva_es_mas_reciente = nueva.ultima_vez > actual.ultima_vez;
+        actual.primera_vez = actual.primera_vez.min(nueva.primera_vez);
+        actual.ultima_vez = actual.ultima_vez.max(nueva.ultima_vez);
+        actual.resultado_publicado = match (actual.resultado_publicado, nueva.resultado_publicado) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        if actual.resultado.is_empty() || (nueva_es_mas_reciente && !nueva.resultado.is_empty()) {
+            actual.resultado = nueva.resultado;
+        }
+
+        let mut fechas = std::mem::take(&mut actual.fechas);
+        fechas.extend(nueva.fechas);
+        fechas.sort_by_key(|f| f.visto);
+        for fecha in fechas {
+            let repetida = actual
+                .fechas
+                .last()
+                .is_some_and(|f| f.data == fecha.data && f.hora == fecha.hora);
+            if !repetida {
+                actual.fechas.push(fecha);
+            }
+        }
+    }
+}
+
+pub fn formatear(instante: i64) -> String {
+    Local
+        .timestamp_opt(instante, 0)
+        .single()
+        .map(|d| d.format("%d/%m/%Y %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pruebas::Temporal;
+    use git2::{Signature, Time};
+
+    fn partido(data: &str, hora: &str, resultado: &str) -> Partido {
+        Partido {
+            competicion: "COPA CATALUNYA".to_string(),
+            data: data.to_string(),
+            hora: hora.to_string(),
+            local: "CP VIC".to_string(),
+            visitante: "CE NOIA".to_string(),
+            resultado: resultado.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Hace un commit de `partidos.json` con `contenido` en el instante `visto`.
+    fn commit(repo: &Repository, visto: i64, contenido: &str) {
+        let ruta = repo.workdir().unwrap().join("partidos.json");
+        std::fs::write(ruta, contenido).unwrap();
+        let mut indice = repo.index().unwrap();
+        indice.add_path(Path::new("partidos.json")).unwrap();
+        indice.write().unwrap();
+        let arbol = repo.find_tree(indice.write_tree().unwrap()).unwrap();
+        let firma = Signature::new("scraper", "scraper@example.com", &Time::new(visto, 0)).unwrap();
+        let padre = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let padres: Vec<&git2::Commit> = padre.iter().collect();
+        repo.commit(Some("HEAD"), &firma, &firma, "agenda", &arbol, &padres)
+            .unwrap();
+    }
+
+    fn version(partidos: &[Partido]) -> String {
+        serde_json::to_string(partidos).unwrap()
+    }
+
+    #[test]
+    fn importa_aplazamientos_y_resultados_de_los_commits() {
+        let dir = Temporal::nuevo("historico-git");
+        let repo = Repository::init(&dir.0).unwrap();
+        let ida = partido("04/04/2026", "18:00", "");
+        commit(&repo, 1_000, &version(&[ida]));
+        // Se aplaza la ida y aparece el desempate, con el mismo local.
+        let aplazada = partido("11/04/2026", "18:00", "");
+        let desempate = partido("25/04/2026", "20:00", "");
+        commit(
+            &repo,
+            2_000,
+            &version(&[aplazada.clone(), desempate.clone()]),
+        );
+        commit(&repo, 2_500, "{ roto");
+        let jugada = partido("11/04/2026", "18:00", "3-1");
+        commit(&repo, 3_000, &version(&[jugada.clone(), desempate.clone()]));
+
+        let (historico, informe) = importar_git(&dir.0, "partidos.json").unwrap();
+        assert_eq!(
+            (informe.commits, informe.versiones, informe.ilegibles),
+            (4, 3, 1)
+        );
+        let claves = claves(&[jugada.clone(), desempate.clone()]);
+        assert_eq!(claves[1], format!("{}#2", claves[0]));
+        assert_eq!(
+            clave(&[desempate.clone(), jugada.clone()], &desempate),
+            claves[1]
+        );
+
+        let ida = &historico[&claves[0]];
+        assert_eq!((ida.primera_vez, ida.ultima_vez), (1_000, 3_000));
+        assert_eq!(ida.aplazamientos(), 1);
+        assert_eq!(ida.fechas[1].visto, 2_000);
+        assert_eq!(
+            (ida.resultado.as_str(), ida.resultado_publicado),
+            ("3-1", Some(3_000))
+        );
+
+        let desempate = &historico[&claves[1]];
+        assert_eq!(desempate.primera_vez, 2_000);
+        assert_eq!(desempate.aplazamientos(), 0);
+        assert_eq!(desempate.resultado_publicado, None);
+    }
+
+    #[test]
+    fn fusionar_no_pierde_lo_visto_antes() {
+        let mut guardado = BTreeMap::new();
+        observar(&mut guardado, &[partido("04/04/2026", "18:00", "")], 500);
+        observar(
+            &mut guardado,
+            &[partido("04/04/2026", "18:00", "3-1")],
+            2_800,
+        );
+
+        let mut importado = BTreeMap::new();
+        observar(&mut importado, &[partido("04/04/2026", "18:00", "")], 1_000);
+        observar(
+            &mut importado,
+            &[partido("04/04/2026", "19:00", "3-1")],
+            2_000,
+        );
+        fusionar(&mut guardado, importado);
+
+        let historia = guardado.values().next().unwrap();
+        assert_eq!((historia.primera_vez, historia.ultima_vez), (500, 2_800));
+        assert_eq!(historia.resultado_publicado, Some(2_000));
+        let horas: Vec<&str> = historia.fechas.iter().map(|f| f.hora.as_str()).collect();
+        assert_eq!(horas, ["18:00", "19:00"]);
+    }
+}
@@ -0,0 +1,224 @@
+use crate::models::Partido;
+use chrono::{Datelike, NaiveDate};
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+
+#[derive(Debug, Clone)]
+pub struct Jornada {
+    pub numero: usize,
+    /// Primer y último día del fin de semana (o entre semana) en que se
+    /// jugó; los partidos aplazados de la jornada pueden caer más tarde.
+    pub inicio: NaiveDate,
+    pub fin: NaiveDate,
+    pub partidos: Vec<Partido>,
+}
+
+/// Partidos de una semana en los que ningún equipo juega dos veces, con el
+/// primer y último día en que se jugaron.
+struct Grupo<'a> {
+    inicio: NaiveDate,
+    fin: NaiveDate,
+    partidos: Vec<(NaiveDate, &'a Partido)>,
+}
+
+fn equipos(p: &Partido) -> [String; 2] {
+    [
+        p.local.trim().to_uppercase(),
+        p.visitante.trim().to_uppercase(),
+    ]
+}
+
+fn comparten_equipo(a: &Partido, b: &Partido) -> bool {
+    let b = equipos(b);
+    equipos(a).iter().any(|e| b.contains(e))
+}
+
+/// Agrupa los partidos de una competición en jornadas. La agenda no publica
+/// el número de jornada, así que se deduce: los partidos de cada semana se
+/// reparten en grupos en los que ningún equipo repite (una jornada entre
+/// semana y otra el sábado son dos) y los grupos con menos partidos de los
+/// que tiene una jornada completa, si caben en otras, se reparten entre
+/// ellas: son aplazamientos o adelantos y cada uno va a la jornada más
+/// cercana en la que no jugó ninguno de los dos equipos. Los partidos sin
+/// fecha no se incluyen.
+pub fn jornadas(todos: &[Partido], competicion: &str) -> Vec<Jornada> {
+    let mut partidos: Vec<(NaiveDate, &Partido)> = todos
+        .iter()
+        .filter(|p| p.competicion == competicion)
+        .filter_map(|p| Some((p.fecha()?, p)))
+        .collect();
+    partidos.sort_by_key(|(fecha, p)| (*fecha, p.hora.clone()));
+    let completa = partidos
+        .iter()
+        .flat_map(|(_, p)| equipos(p))
+        .collect::<BTreeSet<_>>()
+        .len()
+        / 2;
+
+    let mut grupos: Vec<Grupo> = Vec::new();
+    for (fecha, p) in partidos {
+        let semana = fecha.iso_week();
+        let libre = grupos.iter_mut().find(|g| {
+            g.inicio.iso_week() == semana && !g.partidos.iter().any(|(_, q)| comparten_equipo(p, q))
+        });
+        match libre {
+            Some(grupo) => {
+                grupo.fin = fecha;
+                grupo.partidos.push((fecha, p));
+            }
+            None => grupos.push(Grupo {
+                inicio: fecha,
+                fin: fecha,
+                partidos: vec![(fecha, p)],
+            }),
+        }
+    }
+
+    // De los más pequeños a los más grandes y, a igual tamaño, de los más
+    // tardíos a los más tempranos: un aplazado se junta con su jornada y no
+    // al revés.
+    let mut incompletos: Vec<usize> = (0..grupos.len())
+        .filter(|&i| grupos[i].partidos.len() < completa)
+        .collect();
+    incompletos.sort_by_key(|&i| (grupos[i].partidos.len(), Reverse(grupos[i].inicio)));
+    for i in incompletos {
+        let destinos: Option<Vec<usize>> = grupos[i]
+            .partidos
+            .iter()
+            .map(|(fecha, p)| {
+                (0..grupos.len())
+                    .filter(|&j| j != i && !grupos[j].partidos.is_empty())
+                    .filter(|&j| {
+                        !grupos[j]
+                            .partidos
+                            .iter()
+                            .any(|(_, q)| comparten_equipo(p, q))
+                    })
+                    .min_by_key(|&j| {
+                        let inicio = grupos[j].inicio;
+                        ((inicio - *fecha).num_days().abs(), inicio > *fecha)
+                    })
+            })
+            .collect();
+        if let Some(destinos) = destinos {
+            let movidos = std::mem::take(&mut grupos[i].partidos);
+            for (partido, j) in movidos.into_iter().zip(destinos) {
+                grupos[j].partidos.push(partido);
+            }
+        }
+    }
+
+    let mut jornadas: Vec<Jornada> = grupos
+        .into_iter()
+        .filter(|g| !g.partidos.is_empty())
+        .map(|mut g| {
+            g.partidos
+                .sort_by_key(|(fecha, p)| (*fecha, p.hora.clone()));
+            Jornada {
+                numero: 0,
+                inicio: g.inicio,
+                fin: g.fin,
+                partidos: g.partidos.into_iter().map(|(_, p)| p.clone()).collect(),
+            }
+        })
+        .collect();
+    jornadas.sort_by_key(|j| j.inicio);
+    for (i, jornada) in jornadas.iter_mut().enumerate() {
+        jornada.numero = i + 1;
+    }
+    jornadas
+}
+
+/// Jornada en curso: la primera que no ha terminado o, si ya han terminado
+/// todas, la última.
+pub fn jornada_actual(jornadas: &[Jornada], hoy: NaiveDate) -> usize {
+    jornadas
+        .iter()
+        .position(|j| j.fin >= hoy)
+        .unwrap_or(jornadas.len().saturating_sub(1))
+}
+
+/// Si `p` es de alguna de las jornadas hasta la de índice `indice`.
+pub fn hasta_la_jornada(jornadas: &[Jornada], indice: usize, p: &Partido) -> bool {
+    jornadas
+        .iter()
+        .take(indice + 1)
+        .any(|j| j.partidos.contains(p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMPETICION: &str = "BCN ALEVÍ PLATA P3";
+
+    fn partido(data: &str, local: &str, visitante: &str) -> Partido {
+        Partido {
+            competicion: COMPETICION.to_string(),
+            data: data.to_string(),
+            local: local.to_string(),
+            visitante: visitante.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn parejas(jornada: &Jornada) -> Vec<String> {
+        jornada
+            .partidos
+            .iter()
+            .map(|p| format!("{}-{}", p.local, p.visitante))
+            .collect()
+    }
+
+    #[test]
+    fn un_aplazado_se_queda_en_su_jornada() {
+        let partidos = [
+            partido("04/10/2025", "A", "B"),
+            partido("04/10/2025", "C", "D"),
+            partido("11/10/2025", "A", "C"),
+            partido("11/10/2025", "B", "D"),
+            partido("18/10/2025", "A", "D"),
+            // B-C, de la jornada 3, se juega un miércoles dos semanas después.
+            partido("29/10/2025", "B", "C"),
+        ];
+        let jornadas = jornadas(&partidos, COMPETICION);
+        assert_eq!(jornadas.len(), 3);
+        assert_eq!(parejas(&jornadas[2]), ["A-D", "B-C"]);
+        assert_eq!(
+            jornadas[2].fin,
+            NaiveDate::from_ymd_opt(2025, 10, 18).unwrap()
+        );
+        assert!(hasta_la_jornada(&jornadas, 2, &partidos[5]));
+        assert!(!hasta_la_jornada(&jornadas, 1, &partidos[5]));
+    }
+
+    #[test]
+    fn un_adelantado_no_crea_otra_jornada() {
+        let partidos = [
+            partido("27/09/2025", "A", "C"),
+            partido("04/10/2025", "A", "B"),
+            partido("04/10/2025", "C", "D"),
+            partido("11/10/2025", "B", "D"),
+        ];
+        let jornadas = jornadas(&partidos, COMPETICION);
+        assert_eq!(jornadas.len(), 2);
+        assert!(jornadas.iter().any(|j| parejas(j) == ["A-C", "B-D"]));
+    }
+
+    #[test]
+    fn dos_jornadas_en_la_misma_semana() {
+        let partidos = [
+            partido("08/10/2025", "A", "B"),
+            partido("08/10/2025", "C", "D"),
+            partido("11/10/2025", "A", "C"),
+            partido("11/10/2025", "B", "D"),
+        ];
+        let jornadas = jornadas(&partidos, COMPETICION);
+        assert_eq!(jornadas.len(), 2);
+        assert_eq!(parejas(&jornadas[0]), ["A-B", "C-D"]);
+        assert_eq!(
+            jornada_actual(&jornadas, NaiveDate::from_ymd_opt(2025, 10, 9).unwrap()),
+            1
+        );
+    }
+}
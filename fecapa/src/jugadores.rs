@@ -0,0 +1,299 @@
+use crate::models::{Acta, Lado, Partido, TipoEvento};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Tarjetas azules acumuladas que suponen un partido de sanción.
+pub const AZULES_POR_SANCION: u32 = 5;
+
+#[derive(Debug, Clone, Default)]
+pub struct EstadisticaJugador {
+    pub nombre: String,
+    pub equipo: String,
+    pub dorsal: String,
+    pub partidos: u32,
+    pub goles: u32,
+    pub azules: u32,
+    pub rojas: u32,
+}
+
+impl EstadisticaJugador {
+    pub fn goles_por_partido(&self) -> f64 {
+        if self.partidos == 0 {
+            0.0
+        } else {
+            self.goles as f64 / self.partidos as f64
+        }
+    }
+
+    /// Azules que faltan para la próxima sanción por acumulación.
+    pub fn azules_para_sancion(&self) -> u32 {
+        AZULES_POR_SANCION - self.azules % AZULES_POR_SANCION
+    }
+
+    /// A una tarjeta azul de cumplir ciclo.
+    pub fn en_riesgo(&self) -> bool {
+        self.azules_para_sancion() == 1
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum OrdenJugadores {
+    #[default]
+    Goles,
+    GolesPorPartido,
+    Tarjetas,
+    Nombre,
+}
+
+impl OrdenJugadores {
+    pub fn siguiente(self) -> Self {
+        match self {
+            OrdenJugadores::Goles => OrdenJugadores::GolesPorPartido,
+            OrdenJugadores::GolesPorPartido => OrdenJugadores::Tarjetas,
+            OrdenJugadores::Tarjetas => OrdenJugadores::Nombre,
+            OrdenJugadores::Nombre => OrdenJugadores::Goles,
+        }
+    }
+
+    pub fn etiqueta(self) -> &'static str {
+        match self {
+            OrdenJugadores::Goles => "goles",
+            OrdenJugadores::GolesPorPartido => "goles/partido",
+            OrdenJugadores::Tarjetas => "tarjetas",
+            OrdenJugadores::Nombre => "nombre",
+        }
+    }
+}
+
+/// Competiciones con al menos un acta descargada.
+pub fn competiciones(todos: &[Partido], actas: &BTreeMap<String, Acta>) -> Vec<String> {
+    todos
+        .iter()
+        .filter(|p| {
+            p.clave_acta()
+                .is_some_and(|clave| actas.contains_key(clave))
+        })
+        .map(|p| p.competicion.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Suma goles y tarjetas de cada jugador en las actas de `competicion` y, si
+/// se indica, solo los del `equipo`. Un jugador se identifica por equipo y
+/// nombre, ya que el mismo nombre puede aparecer en clubes distintos.
+pub fn calcular(
+    todos: &[Partido],
+    actas: &BTreeMap<String, Acta>,
+    competicion: Option<&str>,
+    equipo: Option<&str>,
+) -> Vec<EstadisticaJugador> {
+    let mut jugadores: BTreeMap<(String, String), EstadisticaJugador> = BTreeMap::new();
+
+    for p in todos {
+        if competicion.is_some_and(|c| c != p.competicion) {
+            continue;
+        }
+        let Some(acta) = p.clave_acta().and_then(|clave| actas.get(clave)) else {
+            continue;
+        };
+
+        for (lado, nombre_equipo, alineacion) in [
+            (Lado::Local, &p.local, &acta.local),
+            (Lado::Visitante, &p.visitante, &acta.visitante),
+        ] {
+            if equipo.is_some_and(|e| !nombre_equipo.to_uppercase().contains(&e.to_uppercase())) {
+                continue;
+            }
+
+            for j in alineacion {
+                let stats = jugadores
+                    .entry((nombre_equipo.clone(), j.nombre.clone()))
+                    .or_insert_with(|| EstadisticaJugador {
+                        nombre: j.nombre.clone(),
+                        equipo: nombre_equipo.clone(),
+                        ..Default::default()
+                    });
+                stats.dorsal = j.dorsal.clone();
+                stats.partidos += 1;
+            }
+
+            for e in acta.eventos.iter().filter(|e| e.lado == lado) {
+                // Un evento de alguien que no figura en la alineación cuenta igual.
+                let stats = jugadores
+                    .entry((nombre_equipo.clone(), e.jugador.clone()))
+                    .or_insert_with(|| EstadisticaJugador {
+                        nombre: e.jugador.clone(),
+                        equipo: nombre_equipo.clone(),
+                        ..Default::default()
+                    });
+                match e.tipo {
+                    TipoEvento::Gol => stats.goles += 1,
+                    TipoEvento::Azul => stats.azules += 1,
+                    TipoEvento::Roja => stats.rojas += 1,
+                }
+            }
+        }
+    }
+
+    jugadores.into_values().collect()
+}
+
+pub fn ordenar(jugadores: &mut [EstadisticaJugador], orden: OrdenJugadores) {
+    match orden {
+        OrdenJugadores::Goles => jugadores.sort_by(|a, b| {
+            b.goles
+                .cmp(&a.goles)
+                .then(a.partidos.cmp(&b.partidos))
+                .then(a.nombre.cmp(&b.nombre))
+        }),
+        OrdenJugadores::GolesPorPartido => jugadores.sort_by(|a, b| {
+            b.goles_por_partido()
+                .total_cmp(&a.goles_por_partido())
+                .then(b.goles.cmp(&a.goles))
+                .then(a.nombre.cmp(&b.nombre))
+        }),
+        OrdenJugadores::Tarjetas => jugadores.sort_by(|a, b| {
+            (b.rojas, b.azules)
+                .cmp(&(a.rojas, a.azules))
+                .then(a.nombre.cmp(&b.nombre))
+        }),
+        OrdenJugadores::Nombre => {
+            jugadores.sort_by(|a, b| a.nombre.cmp(&b.nombre).then(a.equipo.cmp(&b.equipo)))
+        }
+    }
+}
+
+/// Un campo de texto entre comillas si lleva el separador, comillas o un
+/// salto de línea.
+fn campo_csv(texto: &str) -> String {
+    if texto.contains([';', '"', '\n', '\r']) {
+        format!("\"{}\"", texto.replace('"', "\"\""))
+    } else {
+        texto.to_string()
+    }
+}
+
+/// Tabla en CSV (separada por `;` para abrirla directamente en una hoja de
+/// cálculo configurada en español).
+pub fn a_csv(jugadores: &[EstadisticaJugador]) -> String {
+    let mut csv = String::from("jugador;equipo;dorsal;partidos;goles;goles_partido;azules;rojas\n");
+    for j in jugadores {
+        csv.push_str(&format!(
+            "{};{};{};{};{};{:.2};{};{}\n",
+            campo_csv(&j.nombre),
+            campo_csv(&j.equipo),
+            campo_csv(&j.dorsal),
+            j.partidos,
+            j.goles,
+            j.goles_por_partido(),
+            j.azules,
+            j.rojas
+        ));
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{EventoActa, JugadorActa};
+
+    fn partido(enlace: &str) -> Partido {
+        Partido {
+            competicion: "COPA CATALUNYA".to_string(),
+            data: "25/04/2026".to_string(),
+            local: "CP VIC".to_string(),
+            visitante: "CE NOIA".to_string(),
+            resultado: "2-1".to_string(),
+            enlace: enlace.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn acta(url: &str, goles_local: &[&str]) -> Acta {
+        let jugador = |nombre: &str| JugadorActa {
+            dorsal: "7".to_string(),
+            nombre: nombre.to_string(),
+        };
+        Acta {
+            url: url.to_string(),
+            local: vec![jugador("PAU"), jugador("JAN")],
+            visitante: vec![jugador("MARC")],
+            eventos: goles_local
+                .iter()
+                .map(|nombre| EventoActa {
+                    minuto: "10".to_string(),
+                    lado: Lado::Local,
+                    jugador: nombre.to_string(),
+                    tipo: TipoEvento::Gol,
+                })
+                .chain([EventoActa {
+                    minuto: "30".to_string(),
+                    lado: Lado::Visitante,
+                    jugador: "MARC".to_string(),
+                    tipo: TipoEvento::Azul,
+                }])
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn cada_partido_de_una_eliminatoria_usa_su_acta() {
+        // Ida y vuelta con el mismo local: misma clave de cruce, actas distintas.
+        let partidos = [
+            partido("https://fecapa/acta/1"),
+            partido("https://fecapa/acta/2"),
+            partido(""),
+        ];
+        let actas: BTreeMap<String, Acta> = [
+            acta("https://fecapa/acta/1", &["PAU", "PAU"]),
+            acta("https://fecapa/acta/2", &["JAN"]),
+        ]
+        .into_iter()
+        .map(|a| (a.url.clone(), a))
+        .collect();
+
+        let jugadores = calcular(&partidos, &actas, Some("COPA CATALUNYA"), None);
+        let jugador = |nombre: &str| jugadores.iter().find(|j| j.nombre == nombre).unwrap();
+        assert_eq!((jugador("PAU").partidos, jugador("PAU").goles), (2, 2));
+        assert_eq!((jugador("JAN").partidos, jugador("JAN").goles), (2, 1));
+        assert_eq!((jugador("MARC").partidos, jugador("MARC").azules), (2, 2));
+        assert_eq!(jugador("MARC").equipo, "CE NOIA");
+
+        let del_noia = calcular(&partidos, &actas, None, Some("noia"));
+        assert_eq!(del_noia.len(), 1);
+        assert_eq!(competiciones(&partidos, &actas), ["COPA CATALUNYA"]);
+    }
+
+    #[test]
+    fn la_sancion_llega_cada_cinco_azules() {
+        let con = |azules| EstadisticaJugador {
+            azules,
+            ..Default::default()
+        };
+        assert_eq!(con(0).azules_para_sancion(), AZULES_POR_SANCION);
+        assert!(!con(3).en_riesgo());
+        assert!(con(4).en_riesgo());
+        // Con la quinta cumple y el ciclo empieza de nuevo.
+        assert_eq!(con(5).azules_para_sancion(), AZULES_POR_SANCION);
+        assert!(con(9).en_riesgo());
+    }
+
+    #[test]
+    fn el_csv_entrecomilla_los_nombres_con_separadores() {
+        let jugadores = [EstadisticaJugador {
+            nombre: "GARCÍA; \"PITU\"".to_string(),
+            equipo: "CP VIC".to_string(),
+            dorsal: "7".to_string(),
+            partidos: 4,
+            goles: 3,
+            azules: 1,
+            rojas: 0,
+        }];
+        assert_eq!(
+            a_csv(&jugadores).lines().nth(1).unwrap(),
+            "\"GARCÍA; \"\"PITU\"\"\";CP VIC;7;4;3;0.75;1;0"
+        );
+    }
+}
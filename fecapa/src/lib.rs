@@ -0,0 +1,71 @@
+//! Datos de la agenda de hockey patines de la FECAPA: los partidos, su
+//! descarga y validación, los archivos en los que se guardan y los cálculos
+//! que se hacen con ellos (clasificaciones, ratings, estadísticas...).
+//!
+//! Es la parte de `fecapa-explorer` que no depende de la terminal, para
+//! poder usarla desde otros programas:
+//!
+//! ```no_run
+//! use fecapa::{datos, Filtro};
+//!
+//! let partidos = datos::cargar_partidos()?.partidos;
+//! let anotaciones = datos::cargar_anotaciones()?;
+//! let filtro = Filtro {
+//!     nombre: "Alevines".to_string(),
+//!     buscar: "".to_string(),
+//!     categoria: "ALEVÍ".to_string(),
+//!     favoritos: false,
+//! };
+//! for p in filtro.filtrar(&partidos, &anotaciones) {
+//!     println!("{} {} {} - {}", p.data, p.hora, p.local, p.visitante);
+//! }
+//! # Ok::<(), fecapa::ErrorDatos>(())
+//! ```
+//!
+//! Los archivos se buscan en los directorios XDG de la aplicación (o en el
+//! que se indique con [`datos::configurar_directorios`]), los mismos que usa
+//! la interfaz, así que ambos comparten configuración y datos.
+
+/// Actas de los partidos: descarga y lectura de alineaciones, goles y
+/// tarjetas.
+pub mod actas;
+/// Lectura de la agenda de la web de la FECAPA.
+pub mod agenda;
+/// Exportación de los partidos a un calendario iCalendar (`.ics`).
+pub mod calendario;
+/// Clasificación de una competición y proyección de la que queda.
+pub mod clasificacion;
+/// Reglas de las competiciones (`competiciones.json`) y árbol de categorías.
+pub mod competiciones;
+/// Carga y guardado de todos los archivos, con sus directorios y errores.
+pub mod datos;
+/// Historial de enfrentamientos entre dos equipos.
+pub mod enfrentamientos;
+/// Formato versionado de `partidos.json` y validación de filas.
+pub mod esquema;
+/// Estadísticas de un equipo: balance, goles y evolución.
+pub mod estadisticas;
+/// Fuentes de las que se obtienen los partidos (scraper, HTTP, archivos)
+/// y la cadena que las prueba en orden.
+pub mod fuentes;
+/// Cambios de fecha y publicación de resultados a lo largo del tiempo.
+pub mod historico;
+/// Agrupación de los partidos de una competición por jornadas.
+pub mod jornadas;
+/// Goles y tarjetas de cada jugador a partir de las actas.
+pub mod jugadores;
+/// Tipos de datos: partidos, filtros, anotaciones, actas...
+pub mod models;
+/// Pistas y desplazamientos hasta ellas.
+pub mod pistas;
+#[cfg(test)]
+mod pruebas;
+/// Ratings Elo y pronósticos.
+pub mod ratings;
+/// Temporadas y archivo de las anteriores.
+pub mod temporadas;
+
+pub use datos::ErrorDatos;
+pub use esquema::ArchivoPartidos;
+pub use fuentes::{obtener, Ajustes, FuenteDatos};
+pub use models::{Filtro, Partido};
@@ -0,0 +1,358 @@
+use chrono::NaiveDate;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+static RESULTADO: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(\d+)\s*[-:]\s*(\d+)").unwrap());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Filtro {
+    pub nombre: String,
+    pub buscar: String,
+    pub categoria: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub favoritos: bool,
+}
+
+impl Filtro {
+    /// Si `p` pasa el filtro: `buscar` en competición, equipos o pista y
+    /// `categoria` en la competición, sin distinguir mayúsculas. Los filtros
+    /// de favoritos solo miran las anotaciones.
+    pub fn cumple(&self, p: &Partido, anotaciones: &Anotaciones) -> bool {
+        if self.favoritos {
+            return anotaciones.es_favorito(p);
+        }
+        let texto =
+            format!("{} {} {} {}", p.competicion, p.local, p.visitante, p.pista).to_uppercase();
+        texto.contains(&self.buscar.to_uppercase())
+            && p.competicion
+                .to_uppercase()
+                .contains(&self.categoria.to_uppercase())
+    }
+
+    /// Los partidos de `partidos` que pasan el filtro, en el mismo orden.
+    pub fn filtrar(&self, partidos: &[Partido], anotaciones: &Anotaciones) -> Vec<Partido> {
+        partidos
+            .iter()
+            .filter(|p| self.cumple(p, anotaciones))
+            .cloned()
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EquipoConfig {
+    pub filtros: Vec<Filtro>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Origen {
+    #[default]
+    Fecapa,
+    Manual,
+}
+
+impl Origen {
+    pub fn es_fecapa(&self) -> bool {
+        *self == Origen::Fecapa
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Partido {
+    #[serde(rename = "competicio")]
+    pub competicion: String,
+    pub data: String,
+    pub hora: String,
+    pub local: String,
+    #[serde(rename = "visitant")]
+    pub visitante: String,
+    #[serde(rename = "resultat")]
+    pub resultado: String,
+    pub pista: String,
+    #[serde(default, skip_serializing_if = "Origen::es_fecapa")]
+    pub origen: Origen,
+    /// Enlace al acta del partido en la web de la FECAPA, si lo hay.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub enlace: String,
+    /// Temporada (p. ej. "2025-26") de los partidos archivados; en los recién
+    /// descargados va vacía y se deduce de la fecha.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub temporada: String,
+}
+
+impl Partido {
+    /// Identidad estable del partido: no depende de fecha, hora ni resultado,
+    /// así que sobrevive a aplazamientos y refrescos.
+    pub fn clave(&self) -> String {
+        format!(
+            "{}|{}|{}",
+            self.competicion.trim().to_uppercase(),
+            self.local.trim().to_uppercase(),
+            self.visitante.trim().to_uppercase()
+        )
+    }
+
+    /// La clave precedida de la temporada. El mismo cruce se repite cada
+    /// año, así que es la que usan las anotaciones, la disponibilidad y el
+    /// histórico. Sin fecha ni temporada la temporada va vacía.
+    pub fn clave_temporada(&self) -> String {
+        format!(
+            "{}|{}",
+            crate::temporadas::temporada(self).unwrap_or_default(),
+            self.clave()
+        )
+    }
+
+    /// Clave de su acta en `actas.json`: el enlace, que es distinto en cada
+    /// partido aunque los mismos equipos se crucen varias veces en una
+    /// eliminatoria. Sin enlace no hay acta.
+    pub fn clave_acta(&self) -> Option<&str> {
+        Some(self.enlace.as_str()).filter(|enlace| !enlace.is_empty())
+    }
+
+    /// Goles (local, visitante) si el resultado está publicado.
+    pub fn goles(&self) -> Option<(u32, u32)> {
+        let caps = RESULTADO.captures(&self.resultado)?;
+        Some((caps[1].parse().ok()?, caps[2].parse().ok()?))
+    }
+
+    /// Búsqueda libre: `texto` (sin distinguir mayúsculas) en competición,
+    /// equipos, pista o resultado.
+    pub fn contiene(&self, texto: &str) -> bool {
+        format!(
+            "{} {} {} {} {}",
+            self.competicion, self.local, self.visitante, self.pista, self.resultado
+        )
+        .to_uppercase()
+        .contains(&texto.to_uppercase())
+    }
+
+    pub fn juega(&self, equipo: &str) -> bool {
+        let equipo = equipo.trim().to_uppercase();
+        self.local.trim().to_uppercase() == equipo || self.visitante.trim().to_uppercase() == equipo
+    }
+
+    pub fn fecha(&self) -> Option<NaiveDate> {
+        leer_fecha(&self.data)
+    }
+}
+
+/// Fecha en cualquiera de los formatos que usa la federación (y el ISO),
+/// para los partidos y para lo que escribe el usuario.
+pub fn leer_fecha(texto: &str) -> Option<NaiveDate> {
+    ["%d/%m/%Y", "%d-%m-%Y", "%Y-%m-%d", "%d/%m/%y"]
+        .iter()
+        .find_map(|formato| NaiveDate::parse_from_str(texto.trim(), formato).ok())
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Anotaciones {
+    #[serde(default)]
+    pub favoritos: Vec<String>,
+    #[serde(default)]
+    pub notas: BTreeMap<String, String>,
+}
+
+impl Anotaciones {
+    pub fn es_favorito(&self, p: &Partido) -> bool {
+        self.favoritos.contains(&p.clave_temporada())
+    }
+
+    pub fn nota(&self, p: &Partido) -> Option<&String> {
+        self.notas.get(&p.clave_temporada())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Disponibilidad {
+    Si,
+    No,
+    Quiza,
+}
+
+impl Disponibilidad {
+    pub fn siguiente(estado: Option<Disponibilidad>) -> Option<Disponibilidad> {
+        match estado {
+            None => Some(Disponibilidad::Si),
+            Some(Disponibilidad::Si) => Some(Disponibilidad::No),
+            Some(Disponibilidad::No) => Some(Disponibilidad::Quiza),
+            Some(Disponibilidad::Quiza) => None,
+        }
+    }
+}
+
+/// Plantillas por filtro de equipo y disponibilidad por partido y jugador.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Asistencia {
+    #[serde(default)]
+    pub plantillas: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    pub disponibilidad: BTreeMap<String, BTreeMap<String, Disponibilidad>>,
+}
+
+impl Asistencia {
+    pub fn estado(&self, p: &Partido, jugador: &str) -> Option<Disponibilidad> {
+        self.disponibilidad
+            .get(&p.clave_temporada())
+            .and_then(|m| m.get(jugador))
+            .copied()
+    }
+
+    /// Recuento de sí / no / quizá / sin respuesta para un partido.
+    pub fn resumen(&self, p: &Partido, plantilla: &[String]) -> [usize; 4] {
+        let mut resumen = [0; 4];
+        for jugador in plantilla {
+            let i = match self.estado(p, jugador) {
+                Some(Disponibilidad::Si) => 0,
+                Some(Disponibilidad::No) => 1,
+                Some(Disponibilidad::Quiza) => 2,
+                None => 3,
+            };
+            resumen[i] += 1;
+        }
+        resumen
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Coordenadas {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pista {
+    pub nombre: String,
+    #[serde(default)]
+    pub alias: Vec<String>,
+    #[serde(default)]
+    pub direccion: String,
+    #[serde(flatten)]
+    pub coordenadas: Coordenadas,
+}
+
+fn velocidad_por_defecto() -> f64 {
+    60.0
+}
+
+fn factor_ruta_por_defecto() -> f64 {
+    1.3
+}
+
+fn margen_por_defecto() -> i64 {
+    30
+}
+
+/// Registro de pistas editado a mano en `pistas.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigPistas {
+    #[serde(default)]
+    pub casa: Option<Coordenadas>,
+    #[serde(default = "velocidad_por_defecto")]
+    pub velocidad_kmh: f64,
+    #[serde(default = "factor_ruta_por_defecto")]
+    pub factor_ruta: f64,
+    #[serde(default = "margen_por_defecto")]
+    pub margen_minutos: i64,
+    #[serde(default)]
+    pub pistas: Vec<Pista>,
+}
+
+impl Default for ConfigPistas {
+    fn default() -> Self {
+        Self {
+            casa: None,
+            velocidad_kmh: velocidad_por_defecto(),
+            factor_ruta: factor_ruta_por_defecto(),
+            margen_minutos: margen_por_defecto(),
+            pistas: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Lado {
+    Local,
+    Visitante,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TipoEvento {
+    Gol,
+    Azul,
+    Roja,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JugadorActa {
+    pub dorsal: String,
+    pub nombre: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventoActa {
+    pub minuto: String,
+    pub lado: Lado,
+    pub jugador: String,
+    pub tipo: TipoEvento,
+}
+
+/// Acta de un partido: alineaciones, goles, tarjetas y árbitros.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Acta {
+    pub url: String,
+    #[serde(default)]
+    pub arbitros: Vec<String>,
+    #[serde(default)]
+    pub local: Vec<JugadorActa>,
+    #[serde(default)]
+    pub visitante: Vec<JugadorActa>,
+    #[serde(default)]
+    pub eventos: Vec<EventoActa>,
+}
+
+impl Acta {
+    pub fn contar(&self, lado: Lado, jugador: &str, tipo: TipoEvento) -> usize {
+        self.eventos
+            .iter()
+            .filter(|e| e.lado == lado && e.tipo == tipo && e.jugador == jugador)
+            .count()
+    }
+}
+
+/// Un horario con el que el partido apareció en la agenda.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CambioFecha {
+    /// Momento en que se vio por primera vez (segundos Unix).
+    pub visto: i64,
+    pub data: String,
+    pub hora: String,
+}
+
+/// Vida de un partido en la agenda, reconstruida a partir de versiones
+/// sucesivas de `partidos.json`. Los instantes son segundos Unix.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HistoriaPartido {
+    pub primera_vez: i64,
+    pub ultima_vez: i64,
+    /// Horarios sucesivos; el primero es el original.
+    #[serde(default)]
+    pub fechas: Vec<CambioFecha>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub resultado: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resultado_publicado: Option<i64>,
+}
+
+impl HistoriaPartido {
+    pub fn aplazamientos(&self) -> usize {
+        self.fechas.len().saturating_sub(1)
+    }
+}
@@ -0,0 +1,207 @@
+use crate::models::{ConfigPistas, Coordenadas, Partido, Pista};
+use chrono::{Duration, NaiveTime};
+use std::collections::HashSet;
+
+const RADIO_TIERRA_KM: f64 = 6371.0;
+
+/// Palabras que no ayudan a distinguir una pista de otra.
+const PALABRAS_VACIAS: [&str; 12] = [
+    "DE",
+    "DEL",
+    "DELS",
+    "LA",
+    "LES",
+    "EL",
+    "ELS",
+    "L",
+    "D",
+    "I",
+    "PISTA",
+    "MUNICIPAL",
+];
+
+#[derive(Debug, Clone, Copy)]
+pub struct Trayecto {
+    pub distancia_km: f64,
+    pub minutos: i64,
+}
+
+pub fn normalizar(texto: &str) -> String {
+    texto
+        .to_uppercase()
+        .chars()
+        .map(|c| match c {
+            'À' | 'Á' | 'Â' | 'Ä' => 'A',
+            'È' | 'É' | 'Ê' | 'Ë' => 'E',
+            'Ì' | 'Í' | 'Î' | 'Ï' => 'I',
+            'Ò' | 'Ó' | 'Ô' | 'Ö' => 'O',
+            'Ù' | 'Ú' | 'Û' | 'Ü' => 'U',
+            'Ç' => 'C',
+            'Ñ' => 'N',
+            c if c.is_alphanumeric() => c,
+            _ => ' ',
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn palabras(texto: &str) -> HashSet<String> {
+    normalizar(texto)
+        .split(' ')
+        .filter(|p| !p.is_empty() && !PALABRAS_VACIAS.contains(p))
+        .map(|p| p.to_string())
+        .collect()
+}
+
+/// Parecido entre dos nombres de pista, de 0 a 1.
+pub fn similitud(a: &str, b: &str) -> f64 {
+    let (na, nb) = (normalizar(a), normalizar(b));
+    if na.is_empty() || nb.is_empty() {
+        return 0.0;
+    }
+    if na == nb {
+        return 1.0;
+    }
+    // Uno dentro del otro, pero solo con palabras enteras: "VIC" no es
+    // "SERVICIOS" ni "SANT" es "SANTA".
+    let (ca, cb) = (format!(" {} ", na), format!(" {} ", nb));
+    if ca.contains(&cb) || cb.contains(&ca) {
+        return 0.9;
+    }
+
+    let (pa, pb) = (palabras(a), palabras(b));
+    let union = pa.union(&pb).count();
+    if union == 0 {
+        return 0.0;
+    }
+    pa.intersection(&pb).count() as f64 / union as f64
+}
+
+/// Busca la pista registrada que mejor encaja con el texto de la agenda.
+pub fn buscar_pista<'a>(config: &'a ConfigPistas, nombre: &str) -> Option<&'a Pista> {
+    config
+        .pistas
+        .iter()
+        .map(|pista| {
+            let mejor = std::iter::once(&pista.nombre)
+                .chain(pista.alias.iter())
+                .map(|n| similitud(n, nombre))
+                .fold(0.0, f64::max);
+            (pista, mejor)
+        })
+        .filter(|(_, puntuacion)| *puntuacion >= 0.5)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(pista, _)| pista)
+}
+
+/// Distancia en línea recta (fórmula del haversine).
+pub fn distancia_km(a: Coordenadas, b: Coordenadas) -> f64 {
+    let (lat1, lat2) = (a.lat.to_radians(), b.lat.to_radians());
+    let dlat = (b.lat - a.lat).to_radians();
+    let dlon = (b.lon - a.lon).to_radians();
+    let h = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    2.0 * RADIO_TIERRA_KM * h.sqrt().asin()
+}
+
+pub fn trayecto(config: &ConfigPistas, p: &Partido) -> Option<Trayecto> {
+    let casa = config.casa?;
+    let pista = buscar_pista(config, &p.pista)?;
+    let distancia_km = distancia_km(casa, pista.coordenadas);
+    let horas = distancia_km * config.factor_ruta / config.velocidad_kmh.max(1.0);
+    Some(Trayecto {
+        distancia_km,
+        minutos: (horas * 60.0).round() as i64,
+    })
+}
+
+/// Hora a la que hay que salir de casa para llegar con el margen configurado.
+pub fn hora_salida(config: &ConfigPistas, p: &Partido, trayecto: &Trayecto) -> Option<NaiveTime> {
+    let hora = NaiveTime::parse_from_str(p.hora.trim(), "%H:%M").ok()?;
+    Some(hora - Duration::minutes(trayecto.minutos + config.margen_minutos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ConfigPistas {
+        let pista = |nombre: &str, alias: &[&str], lat, lon| Pista {
+            nombre: nombre.to_string(),
+            alias: alias.iter().map(|a| a.to_string()).collect(),
+            direccion: String::new(),
+            coordenadas: Coordenadas { lat, lon },
+        };
+        ConfigPistas {
+            casa: Some(Coordenadas {
+                lat: 41.6897,
+                lon: 2.4937,
+            }),
+            pistas: vec![
+                pista("Pavelló Castell d'en Planes", &["VIC"], 41.9301, 2.2549),
+                pista("Pavelló Municipal Sant Celoni", &[], 41.6897, 2.4937),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn partido(pista: &str, hora: &str) -> Partido {
+        Partido {
+            pista: pista.to_string(),
+            hora: hora.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn la_similitud_solo_cuenta_palabras_enteras() {
+        assert_eq!(similitud("Pavelló de Vic", "PAVELLO DE VIC"), 1.0);
+        assert_eq!(similitud("VIC", "PAV. MUNICIPAL VIC"), 0.9);
+        assert!(similitud("VIC", "PAV. SERVICIOS MUNICIPALES") < 0.5);
+        assert!(similitud("SANT", "PISTA SANTA PERPETUA") < 0.5);
+        // Sin contener uno al otro, por palabras en común.
+        assert_eq!(similitud("Pavelló Sant Celoni", "Sant Celoni Pavelló"), 1.0);
+        assert_eq!(similitud("", "VIC"), 0.0);
+    }
+
+    #[test]
+    fn busca_por_nombre_y_alias() {
+        let config = config();
+        let pista = |nombre| buscar_pista(&config, nombre).map(|p| p.nombre.as_str());
+        assert_eq!(pista("PAV. VIC"), Some("Pavelló Castell d'en Planes"));
+        assert_eq!(
+            pista("PAV. MUNICIPAL SANT CELONI"),
+            Some("Pavelló Municipal Sant Celoni")
+        );
+        assert_eq!(pista("POLIESPORTIU DE SERVICIOS"), None);
+    }
+
+    #[test]
+    fn distancia_y_hora_de_salida() {
+        let barcelona = Coordenadas {
+            lat: 41.3851,
+            lon: 2.1734,
+        };
+        let madrid = Coordenadas {
+            lat: 40.4168,
+            lon: -3.7038,
+        };
+        assert!((distancia_km(barcelona, madrid) - 505.4).abs() < 0.1);
+        assert_eq!(distancia_km(madrid, madrid), 0.0);
+
+        let config = config();
+        let trayecto = trayecto(&config, &partido("PAV. VIC", "18:00")).unwrap();
+        assert!((trayecto.distancia_km - 33.3).abs() < 0.1);
+        // 33,26 km × 1,3 a 60 km/h.
+        assert_eq!(trayecto.minutos, 43);
+        assert_eq!(
+            hora_salida(&config, &partido("PAV. VIC", "18:00"), &trayecto),
+            NaiveTime::from_hms_opt(16, 47, 0)
+        );
+        assert_eq!(
+            hora_salida(&config, &partido("PAV. VIC", "A CONFIRMAR"), &trayecto),
+            None
+        );
+    }
+}
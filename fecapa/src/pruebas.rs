@@ -0,0 +1,22 @@
+//! Utilidades compartidas por las pruebas.
+
+use std::fs;
+use std::path::PathBuf;
+
+/// Directorio temporal de una prueba; se borra al terminar.
+pub struct Temporal(pub PathBuf);
+
+impl Temporal {
+    pub fn nuevo(nombre: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("fecapa-{}-{}", nombre, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        Temporal(dir)
+    }
+}
+
+impl Drop for Temporal {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
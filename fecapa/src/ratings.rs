@@ -0,0 +1,343 @@
+use crate::models::Partido;
+use std::collections::HashMap;
+
+const RATING_INICIAL: f64 = 1500.0;
+const FACTOR_K: f64 = 30.0;
+const VENTAJA_LOCAL: f64 = 40.0;
+/// Proporción de empates que se supone mientras no hay partidos jugados.
+const TASA_EMPATES_INICIAL: f64 = 0.15;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Desenlace {
+    Local,
+    Empate,
+    Visitante,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Pronostico {
+    pub local: f64,
+    pub empate: f64,
+    pub visitante: f64,
+}
+
+impl Pronostico {
+    pub fn favorito(&self) -> Desenlace {
+        if self.local >= self.empate && self.local >= self.visitante {
+            Desenlace::Local
+        } else if self.visitante >= self.empate {
+            Desenlace::Visitante
+        } else {
+            Desenlace::Empate
+        }
+    }
+
+    fn probabilidad(&self, desenlace: Desenlace) -> f64 {
+        match desenlace {
+            Desenlace::Local => self.local,
+            Desenlace::Empate => self.empate,
+            Desenlace::Visitante => self.visitante,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RatingEquipo {
+    pub equipo: String,
+    pub rating: f64,
+    pub jugados: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Competicion {
+    equipos: HashMap<String, RatingEquipo>,
+    jugados: usize,
+    empates: usize,
+}
+
+impl Competicion {
+    fn rating(&self, equipo: &str) -> f64 {
+        self.equipos
+            .get(&clave_equipo(equipo))
+            .map(|r| r.rating)
+            .unwrap_or(RATING_INICIAL)
+    }
+
+    fn tasa_empates(&self) -> f64 {
+        if self.jugados < 10 {
+            TASA_EMPATES_INICIAL
+        } else {
+            self.empates as f64 / self.jugados as f64
+        }
+    }
+
+    fn pronostico(&self, p: &Partido) -> Pronostico {
+        let esperado = esperado(
+            self.rating(&p.local) + VENTAJA_LOCAL,
+            self.rating(&p.visitante),
+        );
+        // El empate es más probable cuanto más igualado está el partido.
+        let empate = self.tasa_empates() * 1.5 * (1.0 - (2.0 * esperado - 1.0).powi(2));
+        let empate = empate.clamp(0.0, 0.5);
+        let local = (esperado - empate / 2.0).max(0.0);
+        let visitante = (1.0 - esperado - empate / 2.0).max(0.0);
+        // Con muchos empates el recorte a cero puede dejar la suma por
+        // encima de 1.
+        let total = local + empate + visitante;
+        Pronostico {
+            local: local / total,
+            empate: empate / total,
+            visitante: visitante / total,
+        }
+    }
+
+    fn actualizar(&mut self, p: &Partido, goles_local: u32, goles_visitante: u32) {
+        let resultado = match goles_local.cmp(&goles_visitante) {
+            std::cmp::Ordering::Greater => 1.0,
+            std::cmp::Ordering::Equal => 0.5,
+            std::cmp::Ordering::Less => 0.0,
+        };
+        let esperado = esperado(
+            self.rating(&p.local) + VENTAJA_LOCAL,
+            self.rating(&p.visitante),
+        );
+        let margen = ((goles_local.abs_diff(goles_visitante) + 1) as f64)
+            .ln()
+            .max(1.0);
+        let cambio = FACTOR_K * margen * (resultado - esperado);
+
+        for (nombre, signo) in [(&p.local, 1.0), (&p.visitante, -1.0)] {
+            let entrada =
+                self.equipos
+                    .entry(clave_equipo(nombre))
+                    .or_insert_with(|| RatingEquipo {
+                        equipo: nombre.trim().to_string(),
+                        rating: RATING_INICIAL,
+                        jugados: 0,
+                    });
+            entrada.rating += signo * cambio;
+            entrada.jugados += 1;
+        }
+
+        self.jugados += 1;
+        if goles_local == goles_visitante {
+            self.empates += 1;
+        }
+    }
+}
+
+fn clave_equipo(equipo: &str) -> String {
+    equipo.trim().to_uppercase()
+}
+
+fn esperado(rating_a: f64, rating_b: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((rating_b - rating_a) / 400.0))
+}
+
+fn desenlace(goles_local: u32, goles_visitante: u32) -> Desenlace {
+    match goles_local.cmp(&goles_visitante) {
+        std::cmp::Ordering::Greater => Desenlace::Local,
+        std::cmp::Ordering::Equal => Desenlace::Empate,
+        std::cmp::Ordering::Less => Desenlace::Visitante,
+    }
+}
+
+/// Partidos jugados en orden cronológico, con sus goles.
+fn jugados(partidos: &[Partido]) -> Vec<(&Partido, u32, u32)> {
+    let mut jugados: Vec<(&Partido, u32, u32)> = partidos
+        .iter()
+        .filter(|p| p.fecha().is_some())
+        .filter_map(|p| p.goles().map(|(l, v)| (p, l, v)))
+        .collect();
+    jugados.sort_by_key(|(p, _, _)| (p.fecha(), p.hora.clone()));
+    jugados
+}
+
+/// Ratings Elo calculados por separado para cada competición.
+#[derive(Debug, Clone, Default)]
+pub struct Ratings {
+    competiciones: HashMap<String, Competicion>,
+}
+
+impl Ratings {
+    pub fn calcular(partidos: &[Partido]) -> Self {
+        let mut ratings = Ratings::default();
+        for (p, l, v) in jugados(partidos) {
+            ratings
+                .competiciones
+                .entry(p.competicion.clone())
+                .or_default()
+                .actualizar(p, l, v);
+        }
+        ratings
+    }
+
+    pub fn pronostico(&self, p: &Partido) -> Pronostico {
+        match self.competiciones.get(&p.competicion) {
+            Some(competicion) => competicion.pronostico(p),
+            None => Competicion::default().pronostico(p),
+        }
+    }
+
+    pub fn competiciones(&self) -> Vec<&String> {
+        let mut nombres: Vec<&String> = self.competiciones.keys().collect();
+        nombres.sort();
+        nombres
+    }
+
+    /// Tabla de ratings de una competición, de mayor a menor.
+    pub fn tabla(&self, competicion: &str) -> Vec<RatingEquipo> {
+        let mut tabla: Vec<RatingEquipo> = self
+            .competiciones
+            .get(competicion)
+            .map(|c| c.equipos.values().cloned().collect())
+            .unwrap_or_default();
+        tabla.sort_by(|a, b| b.rating.total_cmp(&a.rating));
+        tabla
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Backtest {
+    pub partidos: usize,
+    pub aciertos: usize,
+    pub aciertos_local: usize,
+    pub brier: f64,
+    pub por_competicion: Vec<(String, usize, usize)>,
+}
+
+impl Backtest {
+    pub fn precision(&self) -> f64 {
+        if self.partidos == 0 {
+            0.0
+        } else {
+            self.aciertos as f64 / self.partidos as f64
+        }
+    }
+}
+
+/// Pronostica cada partido jugado con los ratings previos a él y compara
+/// con el resultado real. Con partidos de varias temporadas cada una empieza
+/// de cero, igual que los ratings de la aplicación.
+pub fn backtest(partidos: &[Partido]) -> Backtest {
+    let mut competiciones: HashMap<String, Competicion> = HashMap::new();
+    let mut por_competicion: HashMap<String, (usize, usize)> = HashMap::new();
+    let mut informe = Backtest::default();
+
+    for (p, l, v) in jugados(partidos) {
+        let nombre = if p.temporada.is_empty() {
+            p.competicion.clone()
+        } else {
+            format!("{} {}", p.temporada, p.competicion)
+        };
+        let competicion = competiciones.entry(nombre.clone()).or_default();
+        let real = desenlace(l, v);
+
+        // Los primeros partidos de cada equipo no dicen nada del modelo.
+        let con_historial = [&p.local, &p.visitante].iter().all(|e| {
+            competicion
+                .equipos
+                .get(&clave_equipo(e))
+                .is_some_and(|r| r.jugados > 0)
+        });
+        if con_historial {
+            let pronostico = competicion.pronostico(p);
+            let acierto = pronostico.favorito() == real;
+            informe.partidos += 1;
+            informe.aciertos += acierto as usize;
+            informe.aciertos_local += (real == Desenlace::Local) as usize;
+            informe.brier += [Desenlace::Local, Desenlace::Empate, Desenlace::Visitante]
+                .iter()
+                .map(|d| {
+                    let observado = if *d == real { 1.0 } else { 0.0 };
+                    (pronostico.probabilidad(*d) - observado).powi(2)
+                })
+                .sum::<f64>();
+            let entrada = por_competicion.entry(nombre).or_default();
+            entrada.0 += 1;
+            entrada.1 += acierto as usize;
+        }
+
+        competicion.actualizar(p, l, v);
+    }
+
+    if informe.partidos > 0 {
+        informe.brier /= informe.partidos as f64;
+    }
+    informe.por_competicion = por_competicion
+        .into_iter()
+        .map(|(c, (n, a))| (c, n, a))
+        .collect();
+    informe.por_competicion.sort();
+    informe
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn partido(data: &str, local: &str, visitante: &str, resultado: &str) -> Partido {
+        Partido {
+            competicion: "BCN ALEVÍ OR P4".to_string(),
+            data: data.to_string(),
+            local: local.to_string(),
+            visitante: visitante.to_string(),
+            resultado: resultado.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn una_victoria_local_entre_iguales() {
+        let ratings = Ratings::calcular(&[partido("04/10/2025", "CP VIC", "CP MANLLEU", "3-1")]);
+        let tabla = ratings.tabla("BCN ALEVÍ OR P4");
+        // Esperado del local con 40 de ventaja: 1 / (1 + 10^(-40/400)).
+        let esperado = 1.0 / (1.0 + 10f64.powf(-0.1));
+        let cambio = FACTOR_K * 3f64.ln().max(1.0) * (1.0 - esperado);
+        assert_eq!(tabla[0].equipo, "CP VIC");
+        assert!((tabla[0].rating - (RATING_INICIAL + cambio)).abs() < 1e-9);
+        assert!((tabla[1].rating - (RATING_INICIAL - cambio)).abs() < 1e-9);
+        assert_eq!(tabla[1].jugados, 1);
+    }
+
+    #[test]
+    fn las_probabilidades_suman_uno() {
+        for empates in [0, 5, 10] {
+            let mut competicion = Competicion::default();
+            let mut jugados = vec![partido("04/10/2025", "A", "B", "9-0"); 10 - empates];
+            jugados.extend(vec![partido("04/10/2025", "A", "C", "2-2"); empates]);
+            for p in &jugados {
+                let (l, v) = p.goles().unwrap();
+                competicion.actualizar(p, l, v);
+            }
+            for (local, visitante) in [("A", "B"), ("B", "A"), ("A", "C"), ("D", "E")] {
+                let p = competicion.pronostico(&partido("", local, visitante, ""));
+                assert!((p.local + p.empate + p.visitante - 1.0).abs() < 1e-9);
+                assert!([p.local, p.empate, p.visitante].iter().all(|x| *x >= 0.0));
+            }
+        }
+    }
+
+    #[test]
+    fn el_backtest_empieza_cada_temporada_de_cero() {
+        let mut partidos = Vec::new();
+        for temporada in ["2024-25", "2025-26"] {
+            for (data, resultado) in [("04/10", "3-1"), ("11/10", "1-4"), ("18/10", "2-0")] {
+                partidos.push(Partido {
+                    temporada: temporada.to_string(),
+                    ..partido(
+                        &format!("{}/{}", data, &temporada[..4]),
+                        "A",
+                        "B",
+                        resultado,
+                    )
+                });
+            }
+        }
+        let informe = backtest(&partidos);
+        // El primer partido de cada temporada no tiene historial previo.
+        assert_eq!(informe.partidos, 4);
+        assert_eq!(informe.por_competicion.len(), 2);
+        assert_eq!(informe.por_competicion[0].0, "2024-25 BCN ALEVÍ OR P4");
+    }
+}
@@ -0,0 +1,350 @@
+use crate::clasificacion;
+use crate::estadisticas::{self, Balance};
+use crate::models::{Anotaciones, Asistencia, Origen, Partido};
+use chrono::{Datelike, NaiveDate};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Mes en que empieza una temporada: la pretemporada de agosto ya cuenta
+/// como la temporada siguiente.
+pub const MES_INICIO: u32 = 8;
+
+/// Nombre de la temporada a la que pertenece una fecha, p. ej. "2025-26".
+pub fn temporada_de(fecha: NaiveDate) -> String {
+    let inicio = if fecha.month() >= MES_INICIO {
+        fecha.year()
+    } else {
+        fecha.year() - 1
+    };
+    format!("{}-{:02}", inicio, (inicio + 1) % 100)
+}
+
+pub fn temporada(p: &Partido) -> Option<String> {
+    if !p.temporada.is_empty() {
+        return Some(p.temporada.clone());
+    }
+    p.fecha().map(temporada_de)
+}
+
+/// Reparte los partidos por temporada dejándola anotada en cada uno. Los
+/// partidos sin fecha van a la temporada más reciente.
+pub fn agrupar(partidos: &[Partido]) -> BTreeMap<String, Vec<Partido>> {
+    let mut temporadas: BTreeMap<String, Vec<Partido>> = BTreeMap::new();
+    let mut sin_fecha = Vec::new();
+    for p in partidos {
+        match temporada(p) {
+            Some(t) => temporadas.entry(t.clone()).or_default().push(Partido {
+                temporada: t,
+                ..p.clone()
+            }),
+            None => sin_fecha.push(p.clone()),
+        }
+    }
+    if let Some((t, lista)) = temporadas.iter_mut().next_back() {
+        for p in sin_fecha {
+            lista.push(Partido {
+                temporada: t.clone(),
+                ..p
+            });
+        }
+    }
+    temporadas
+}
+
+/// Añade al archivo de una temporada los partidos nuevos. Un partido ya
+/// archivado se sustituye por su versión nueva (aplazamientos, resultados);
+/// los que ya no salen en la agenda se conservan.
+///
+/// La clave no basta para reconocerlos: en una eliminatoria los mismos
+/// equipos pueden jugar varias veces en la misma pista. Primero se buscan
+/// por el enlace al acta y después, por orden, entre los archivados con la
+/// misma clave que aún no tienen pareja, así que el segundo partido con una
+/// clave va con el segundo archivado.
+pub fn fusionar(archivo: &mut Vec<Partido>, nuevos: Vec<Partido>) -> bool {
+    let mut emparejados = vec![false; archivo.len()];
+    let mut destinos: Vec<Option<usize>> = nuevos
+        .iter()
+        .map(|nuevo| {
+            let i = (!nuevo.enlace.is_empty())
+                .then(|| archivo.iter().position(|p| p.enlace == nuevo.enlace))
+                .flatten()?;
+            (!emparejados[i]).then(|| {
+                emparejados[i] = true;
+                i
+            })
+        })
+        .collect();
+    for (nuevo, destino) in nuevos.iter().zip(destinos.iter_mut()) {
+        if destino.is_some() {
+            continue;
+        }
+        *destino = (0..archivo.len()).find(|&i| {
+            let p = &archivo[i];
+            !emparejados[i]
+                && p.clave() == nuevo.clave()
+                && (p.enlace.is_empty() || nuevo.enlace.is_empty())
+        });
+        if let Some(i) = *destino {
+            emparejados[i] = true;
+        }
+    }
+
+    let mut cambiado = false;
+    for (nuevo, destino) in nuevos.into_iter().zip(destinos) {
+        match destino {
+            Some(i) if archivo[i] == nuevo => {}
+            Some(i) => {
+                archivo[i] = nuevo;
+                cambiado = true;
+            }
+            None => {
+                archivo.push(nuevo);
+                cambiado = true;
+            }
+        }
+    }
+    cambiado
+}
+
+/// Incorpora a `archivo` los partidos de la FECAPA de `partidos`, cada uno
+/// en su temporada. Devuelve las temporadas que han cambiado.
+pub fn incorporar(
+    archivo: &mut BTreeMap<String, Vec<Partido>>,
+    partidos: &[Partido],
+) -> Vec<String> {
+    let fecapa: Vec<Partido> = partidos
+        .iter()
+        .filter(|p| p.origen == Origen::Fecapa)
+        .cloned()
+        .collect();
+    let mut cambiadas = Vec::new();
+    for (temporada, nuevos) in agrupar(&fecapa) {
+        if fusionar(archivo.entry(temporada.clone()).or_default(), nuevos) {
+            cambiadas.push(temporada);
+        }
+    }
+    cambiadas
+}
+
+/// Claves de antes de separar por temporada (`COMPETICIÓN|LOCAL|VISITANTE`)
+/// con la clave con temporada que les toca: la de la temporada más reciente
+/// en la que se juega ese cruce.
+pub fn claves_sin_temporada<'a>(
+    partidos: impl IntoIterator<Item = &'a Partido>,
+) -> BTreeMap<String, String> {
+    let mut claves: BTreeMap<String, String> = BTreeMap::new();
+    for p in partidos {
+        let nueva = p.clave_temporada();
+        let actual = claves.entry(p.clave()).or_insert_with(|| nueva.clone());
+        if nueva > *actual {
+            *actual = nueva;
+        }
+    }
+    claves
+}
+
+/// Cambia en `mapa` las claves antiguas por las de `claves_sin_temporada`.
+/// Si la nueva ya está se queda lo que había en ella. Devuelve si ha
+/// cambiado algo.
+pub fn migrar_claves<T>(mapa: &mut BTreeMap<String, T>, claves: &BTreeMap<String, String>) -> bool {
+    let antiguas: Vec<String> = mapa
+        .keys()
+        .filter(|k| claves.contains_key(*k))
+        .cloned()
+        .collect();
+    for antigua in &antiguas {
+        if let Some(valor) = mapa.remove(antigua) {
+            mapa.entry(claves[antigua].clone()).or_insert(valor);
+        }
+    }
+    !antiguas.is_empty()
+}
+
+/// Pasa los favoritos, notas y disponibilidad guardados con claves de antes
+/// de separar por temporada a las de `claves_sin_temporada`. Solo cambia lo
+/// cargado: se guarda con la siguiente modificación.
+pub fn migrar_anotaciones<'a>(
+    partidos: impl IntoIterator<Item = &'a Partido>,
+    anotaciones: &mut Anotaciones,
+    asistencia: &mut Asistencia,
+) {
+    let claves = claves_sin_temporada(partidos);
+    migrar_claves(&mut anotaciones.notas, &claves);
+    for favorito in anotaciones.favoritos.iter_mut() {
+        if let Some(nueva) = claves.get(favorito) {
+            *favorito = nueva.clone();
+        }
+    }
+    let mut vistos = BTreeSet::new();
+    anotaciones.favoritos.retain(|f| vistos.insert(f.clone()));
+    migrar_claves(&mut asistencia.disponibilidad, &claves);
+}
+
+/// Temporadas disponibles, de la más antigua a la más reciente.
+pub fn temporadas(archivo: &BTreeMap<String, Vec<Partido>>, manuales: &[Partido]) -> Vec<String> {
+    archivo
+        .keys()
+        .cloned()
+        .chain(manuales.iter().filter_map(temporada))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Partidos de una temporada: los archivados más los manuales de esas fechas.
+/// Los manuales sin fecha se muestran siempre.
+pub fn partidos_de(
+    archivo: &BTreeMap<String, Vec<Partido>>,
+    manuales: &[Partido],
+    nombre: &str,
+) -> Vec<Partido> {
+    let mut partidos = archivo.get(nombre).cloned().unwrap_or_default();
+    partidos.extend(
+        manuales
+            .iter()
+            .filter(|p| temporada(p).is_none_or(|t| t == nombre))
+            .cloned(),
+    );
+    partidos
+}
+
+#[derive(Debug, Clone)]
+pub struct HistorialTemporada {
+    pub temporada: String,
+    pub equipo: String,
+    pub competicion: String,
+    pub balance: Balance,
+    /// Posición en la clasificación y número de equipos.
+    pub posicion: Option<(usize, usize)>,
+}
+
+/// Trayectoria de un equipo temporada a temporada. El nombre exacto del
+/// equipo puede cambiar de una a otra (letra del equipo, patrocinador), así
+/// que se busca en cada temporada igual que en los filtros.
+pub fn historial(
+    archivo: &BTreeMap<String, Vec<Partido>>,
+    buscar: &str,
+) -> Vec<HistorialTemporada> {
+    let mut historial = Vec::new();
+    for (nombre, partidos) in archivo.iter().rev() {
+        let Some(equipo) = estadisticas::equipo_del_filtro(partidos, buscar) else {
+            continue;
+        };
+        let competiciones: BTreeSet<&String> = partidos
+            .iter()
+            .filter(|p| p.juega(&equipo))
+            .map(|p| &p.competicion)
+            .collect();
+        for competicion in competiciones {
+            let filas = clasificacion::clasificacion(partidos, competicion);
+            let Some(i) = filas
+                .iter()
+                .position(|f| f.equipo.eq_ignore_ascii_case(&equipo))
+            else {
+                continue;
+            };
+            let jugados = filas[i].balance.jugados > 0;
+            historial.push(HistorialTemporada {
+                temporada: nombre.clone(),
+                equipo: equipo.clone(),
+                competicion: competicion.clone(),
+                balance: filas[i].balance.clone(),
+                posicion: jugados.then_some((i + 1, filas.len())),
+            });
+        }
+    }
+    historial
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn partido(data: &str, resultado: &str, enlace: &str) -> Partido {
+        Partido {
+            competicion: "CAT JÚNIOR OR PLAY-OFF".to_string(),
+            data: data.to_string(),
+            local: "CP VIC".to_string(),
+            visitante: "CP MANLLEU".to_string(),
+            resultado: resultado.to_string(),
+            enlace: enlace.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn dos_partidos_con_la_misma_clave_no_se_juntan() {
+        // Partidos 1 y 3 de la eliminatoria, los dos en la pista del CP VIC.
+        let mut archivo = Vec::new();
+        assert!(fusionar(
+            &mut archivo,
+            vec![partido("05/04/2026", "", ""), partido("19/04/2026", "", "")]
+        ));
+        assert_eq!(archivo.len(), 2);
+
+        // Se juega el primero (ya con acta) y el tercero cambia de fecha.
+        assert!(fusionar(
+            &mut archivo,
+            vec![
+                partido("05/04/2026", "4-2", "acta?id=1"),
+                partido("26/04/2026", "", ""),
+            ]
+        ));
+        assert_eq!(archivo.len(), 2);
+        assert_eq!(archivo[0].resultado, "4-2");
+        assert_eq!(archivo[1].data, "26/04/2026");
+
+        // La agenda ya no lista el primero: el tercero no ocupa su sitio.
+        assert!(fusionar(
+            &mut archivo,
+            vec![partido("26/04/2026", "3-3", "acta?id=3")]
+        ));
+        assert_eq!(archivo.len(), 2);
+        assert_eq!(archivo[0].resultado, "4-2");
+        assert_eq!(archivo[1].resultado, "3-3");
+
+        let igual = archivo.clone();
+        assert!(!fusionar(&mut archivo, igual));
+    }
+
+    #[test]
+    fn las_claves_antiguas_pasan_a_la_ultima_temporada() {
+        let anterior = partido("05/04/2025", "4-2", "");
+        let actual = partido("05/04/2026", "", "");
+        assert_ne!(anterior.clave_temporada(), actual.clave_temporada());
+
+        let claves = claves_sin_temporada([&actual, &anterior]);
+        let mut notas = BTreeMap::from([
+            (anterior.clave(), "llevar petos".to_string()),
+            (
+                "CAT JÚNIOR OR|CP VIC|CH SANT CELONI".to_string(),
+                "otra".to_string(),
+            ),
+        ]);
+        assert!(migrar_claves(&mut notas, &claves));
+        assert_eq!(
+            notas.get(&actual.clave_temporada()).unwrap(),
+            "llevar petos"
+        );
+        // Los cruces que no están en el archivo se quedan como estaban.
+        assert_eq!(notas.len(), 2);
+        assert!(!migrar_claves(&mut notas, &claves));
+    }
+
+    #[test]
+    fn los_favoritos_antiguos_no_se_duplican() {
+        let actual = partido("05/04/2026", "", "");
+        let mut anotaciones = Anotaciones {
+            favoritos: vec![actual.clave(), actual.clave_temporada()],
+            ..Default::default()
+        };
+        let mut asistencia = Asistencia::default();
+        asistencia
+            .disponibilidad
+            .insert(actual.clave(), BTreeMap::new());
+        migrar_anotaciones([&actual], &mut anotaciones, &mut asistencia);
+        assert_eq!(anotaciones.favoritos, [actual.clave_temporada()]);
+        assert!(asistencia
+            .disponibilidad
+            .contains_key(&actual.clave_temporada()));
+    }
+}
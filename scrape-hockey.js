@@ -1,11 +1,15 @@
 const { chromium } = require('playwright');
 const fs = require('fs');
 
+const URL = 'https://www.hoqueipatins.fecapa.cat/ag/';
+// Versión del formato de partidos.json; lo valida `fecapa-explorer --validar`.
+const VERSION = 1;
+
 async function scrapeMatches() {
   const browser = await chromium.launch();
   const page = await browser.newPage();
   
-  await page.goto('https://www.hoqueipatins.fecapa.cat/ag/', { 
+  await page.goto(URL, { 
     waitUntil: 'networkidle',
     timeout: 60000 
   });
@@ -25,6 +29,7 @@ async function scrapeMatches() {
         const visitant = cells[6]?.textContent?.trim() || '';
         const resultat = cells[7]?.textContent?.trim() || '';
         const pista = cells[8]?.textContent?.trim() || '';
+        const enlace = row.querySelector('a[href]')?.href || '';
         
         if (competicio || local || visitant) {
           data.push({
@@ -34,7 +39,8 @@ async function scrapeMatches() {
             local,
             visitant,
             resultat,
-            pista
+            pista,
+            enlace
           });
         }
       }
@@ -43,11 +49,31 @@ async function scrapeMatches() {
     return data;
   });
   
-  fs.writeFileSync('partidos.json', JSON.stringify(matches, null, 2));
-  console.log(`Guardados ${matches.length} partidos en partidos.json`);
-  
   await browser.close();
+
+  const archivo = JSON.stringify({
+    version: VERSION,
+    extraido: new Date().toISOString(),
+    url: URL,
+    filas: matches.length,
+    partidos: matches
+  }, null, 2);
+  // Sin argumento los partidos van por la salida estándar (así los lee la
+  // aplicación, que es quien escribe su partidos.json). Con un archivo, se
+  // escribe y renombra para no dejarlo a medias.
+  const salida = process.argv[2];
+  if (salida) {
+    fs.writeFileSync(`${salida}.tmp`, archivo);
+    fs.renameSync(`${salida}.tmp`, salida);
+    console.error(`Guardados ${matches.length} partidos en ${salida}`);
+  } else {
+    process.stdout.write(archivo + '\n');
+  }
+  
   return matches;
 }
 
-scrapeMatches().catch(console.error);
+scrapeMatches().catch((error) => {
+  console.error(error);
+  process.exit(1);
+});
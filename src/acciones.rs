@@ -0,0 +1,653 @@
+use crate::state::App;
+use crate::ui;
+use crate::vistas::{ConfirmType, Paleta, Vista, CAMPOS_FORMULARIO};
+use fecapa::models::Origen;
+
+/// Partidos que salta AvPag/RePag.
+const TAMANO_PAGINA: usize = 20;
+
+/// Tope de las repeticiones (`5j`), para que un número largo no desborde.
+const CUENTA_MAXIMA: usize = 9999;
+
+/// Lo que puede pedir el usuario. `teclas.rs` traduce cada tecla a una
+/// acción según la vista y `App::update` la aplica, así que el estado se
+/// puede probar sin terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Accion {
+    Salir,
+    Ayuda,
+    Volver,
+    Aceptar,
+    Subir,
+    Bajar,
+    PaginaArriba,
+    PaginaAbajo,
+    MediaPaginaArriba,
+    MediaPaginaAbajo,
+    Inicio,
+    Fin,
+    Izquierda,
+    Derecha,
+    Siguiente,
+    Anterior,
+    Borrar,
+    Escribir(char),
+    /// Cifra de un número de repeticiones, como el 5 de `5j`.
+    Cifra(u8),
+    Refrescar,
+    Filtros,
+    Buscar,
+    Detalles,
+    NuevoPartido,
+    Favorito,
+    Asistencia,
+    OrdenDistancia,
+    Ratings,
+    Estadisticas,
+    Clasificacion,
+    Jornadas,
+    Jugadores,
+    Temporada,
+    Errores,
+    Historial,
+    GuardarFiltro,
+    Eliminar,
+    Nota,
+    CaraACara,
+    DescargarActa,
+    EditarPartido,
+    FiltrarLocal,
+    FiltrarCompeticion,
+    Disponibilidad,
+    NuevoJugador,
+    Exportar,
+    Ordenar,
+    ApartarArchivo,
+    Comandos,
+    Completar,
+}
+
+/// Índice anterior a `actual`, sin pasar de 0.
+fn anterior(actual: &mut usize, pasos: usize) {
+    *actual = actual.saturating_sub(pasos);
+}
+
+/// Índice siguiente a `actual`, sin pasar del último de `total`.
+fn siguiente(actual: &mut usize, pasos: usize, total: usize) {
+    *actual = (*actual + pasos).min(total.saturating_sub(1));
+}
+
+impl App {
+    /// Aplica una acción al estado. Lo que signifique depende de la vista
+    /// (Tab cambia de pestaña en la clasificación y de partido en la
+    /// disponibilidad, por ejemplo); las acciones que no tienen sentido en
+    /// la vista actual no hacen nada.
+    pub fn update(&mut self, accion: Accion) {
+        if let Accion::Cifra(cifra) = accion {
+            // Un 0 sin cifras delante no empieza una cuenta.
+            if cifra > 0 || self.cuenta.is_some() {
+                let cuenta = self.cuenta.unwrap_or(0) * 10 + cifra as usize;
+                self.cuenta = Some(cuenta.min(CUENTA_MAXIMA));
+            }
+            return;
+        }
+        let cuenta = self.cuenta.take();
+
+        match accion {
+            Accion::Salir => {
+                self.salir = true;
+                return;
+            }
+            Accion::Ayuda if self.vista_actual != Vista::Help => {
+                self.vista_anterior = self.vista_actual;
+                self.vista_actual = Vista::Help;
+                return;
+            }
+            _ => {}
+        }
+
+        match self.vista_actual {
+            Vista::Partidos => self.en_partidos(accion, cuenta),
+            Vista::Comandos => self.en_comandos(accion),
+            Vista::Filtros if self.arbol_activo => self.en_arbol(accion),
+            Vista::Filtros => self.en_filtros(accion),
+            Vista::Detalles => self.en_detalles(accion),
+            Vista::Buscar => self.en_buscar(accion),
+            Vista::Confirm => self.en_confirmacion(accion),
+            Vista::Formulario => self.en_formulario(accion),
+            Vista::Nota => self.en_nota(accion),
+            Vista::Asistencia if self.asistencia_entrada.is_some() => self.en_nuevo_jugador(accion),
+            Vista::Asistencia => self.en_asistencia(accion),
+            Vista::CaraACara => {
+                if accion == Accion::Volver {
+                    self.vista_actual = Vista::Detalles;
+                }
+            }
+            Vista::Ratings => match accion {
+                Accion::Volver => self.vista_actual = Vista::Partidos,
+                Accion::Izquierda => anterior(&mut self.ratings_competicion, 1),
+                Accion::Derecha => siguiente(
+                    &mut self.ratings_competicion,
+                    1,
+                    self.ratings.competiciones().len(),
+                ),
+                _ => {}
+            },
+            Vista::Clasificacion => match accion {
+                Accion::Volver => self.vista_actual = Vista::Partidos,
+                Accion::Izquierda => self.cambiar_competicion_clasificacion(false),
+                Accion::Derecha => self.cambiar_competicion_clasificacion(true),
+                Accion::Siguiente => self.alternar_pestana_clasificacion(),
+                _ => {}
+            },
+            Vista::Jornadas => match accion {
+                Accion::Volver => self.vista_actual = Vista::Partidos,
+                Accion::Izquierda => anterior(&mut self.jornada_actual, 1),
+                Accion::Derecha => siguiente(&mut self.jornada_actual, 1, self.jornadas.len()),
+                _ => {}
+            },
+            Vista::Errores => match accion {
+                Accion::Volver => self.vista_actual = Vista::Partidos,
+                Accion::Subir => anterior(&mut self.error_seleccionado, 1),
+                Accion::Bajar => siguiente(&mut self.error_seleccionado, 1, self.errores.len()),
+                Accion::ApartarArchivo => self.apartar_archivo_error(),
+                _ => {}
+            },
+            Vista::Jugadores => match accion {
+                Accion::Volver => self.vista_actual = Vista::Partidos,
+                Accion::Subir => anterior(&mut self.jugadores_seleccionado, 1),
+                Accion::Bajar => {
+                    siguiente(&mut self.jugadores_seleccionado, 1, self.jugadores.len())
+                }
+                Accion::Izquierda => self.cambiar_competicion_jugadores(false),
+                Accion::Derecha => self.cambiar_competicion_jugadores(true),
+                Accion::Siguiente => self.alternar_ambito_jugadores(),
+                Accion::Ordenar => self.cambiar_orden_jugadores(),
+                Accion::Exportar => self.exportar_jugadores(),
+                _ => {}
+            },
+            Vista::Estadisticas | Vista::Historial => {
+                if accion == Accion::Volver {
+                    self.vista_actual = Vista::Partidos;
+                }
+            }
+            Vista::Help => {
+                if accion == Accion::Volver {
+                    self.vista_actual = self.vista_anterior;
+                }
+            }
+        }
+    }
+
+    fn en_partidos(&mut self, accion: Accion, cuenta: Option<usize>) {
+        let veces = cuenta.unwrap_or(1);
+        let total = self.partidos.len();
+        match accion {
+            Accion::Refrescar => self.refrescar(),
+            Accion::Filtros => self.vista_actual = Vista::Filtros,
+            Accion::Buscar => {
+                self.buscar_texto.clear();
+                self.vista_actual = Vista::Buscar;
+            }
+            Accion::Detalles => self.vista_actual = Vista::Detalles,
+            Accion::NuevoPartido => self.nuevo_partido_manual(),
+            Accion::Favorito => self.alternar_favorito(),
+            Accion::Asistencia => self.abrir_asistencia(),
+            Accion::OrdenDistancia => self.alternar_orden_distancia(),
+            Accion::Ratings => self.abrir_ratings(),
+            Accion::Estadisticas => self.abrir_estadisticas(),
+            Accion::Clasificacion => self.abrir_clasificacion(),
+            Accion::Jornadas => self.abrir_jornadas(),
+            Accion::Jugadores => self.abrir_jugadores(),
+            Accion::Temporada => self.cambiar_temporada(),
+            Accion::Errores => self.abrir_errores(),
+            Accion::Historial => self.abrir_historial(),
+            Accion::Comandos => {
+                self.paleta = Paleta::default();
+                self.vista_actual = Vista::Comandos;
+            }
+            Accion::Subir => anterior(&mut self.partido_seleccionado, veces),
+            Accion::Bajar => siguiente(&mut self.partido_seleccionado, veces, total),
+            Accion::PaginaArriba => anterior(&mut self.partido_seleccionado, TAMANO_PAGINA * veces),
+            Accion::PaginaAbajo => {
+                siguiente(&mut self.partido_seleccionado, TAMANO_PAGINA * veces, total)
+            }
+            Accion::MediaPaginaArriba => {
+                anterior(&mut self.partido_seleccionado, TAMANO_PAGINA / 2 * veces)
+            }
+            Accion::MediaPaginaAbajo => siguiente(
+                &mut self.partido_seleccionado,
+                TAMANO_PAGINA / 2 * veces,
+                total,
+            ),
+            // Con número delante, los dos van a ese partido (contando desde 1).
+            Accion::Inicio | Accion::Fin if cuenta.is_some() => {
+                self.partido_seleccionado = 0;
+                siguiente(&mut self.partido_seleccionado, veces - 1, total);
+            }
+            Accion::Inicio => self.partido_seleccionado = 0,
+            Accion::Fin => self.partido_seleccionado = total.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    fn en_arbol(&mut self, accion: Accion) {
+        match accion {
+            Accion::Volver => self.vista_actual = Vista::Partidos,
+            Accion::Siguiente => self.arbol_activo = false,
+            Accion::Subir => anterior(&mut self.arbol_seleccionado, 1),
+            Accion::Bajar => {
+                let total = self.nodos_arbol().len();
+                siguiente(&mut self.arbol_seleccionado, 1, total);
+            }
+            Accion::Aceptar | Accion::Derecha => self.activar_nodo_arbol(),
+            Accion::Izquierda => self.plegar_nodo_arbol(),
+            Accion::GuardarFiltro => self.guardar_nodo_como_filtro(),
+            _ => {}
+        }
+    }
+
+    fn en_filtros(&mut self, accion: Accion) {
+        match accion {
+            Accion::Siguiente => self.arbol_activo = true,
+            Accion::Volver => self.vista_actual = Vista::Partidos,
+            Accion::Aceptar => {
+                self.aplicar_filtro();
+                self.vista_actual = Vista::Partidos;
+            }
+            // "Todos" no se puede borrar.
+            Accion::Eliminar if self.filtro_seleccionado > 0 => {
+                self.confirmar(ConfirmType::DeleteFilter);
+            }
+            Accion::Subir => anterior(&mut self.filtro_seleccionado, 1),
+            Accion::Bajar => siguiente(&mut self.filtro_seleccionado, 1, self.filtros.len()),
+            _ => {}
+        }
+    }
+
+    fn en_detalles(&mut self, accion: Accion) {
+        match accion {
+            Accion::Volver => {
+                self.vista_actual = Vista::Partidos;
+                self.detalle_seleccion = 0;
+            }
+            Accion::Subir => anterior(&mut self.detalle_seleccion, 1),
+            Accion::Bajar => {
+                let total = ui::num_campos_detalles(self);
+                siguiente(&mut self.detalle_seleccion, 1, total);
+            }
+            Accion::Favorito => self.alternar_favorito(),
+            Accion::Nota => self.editar_nota(),
+            Accion::CaraACara => self.vista_actual = Vista::CaraACara,
+            Accion::DescargarActa => self.descargar_acta(),
+            Accion::EditarPartido if !self.editar_partido_manual() => {
+                self.mensaje = "Solo se pueden editar partidos manuales".to_string();
+            }
+            Accion::Eliminar => {
+                let es_manual = self
+                    .partidos
+                    .get(self.partido_seleccionado)
+                    .is_some_and(|p| p.origen == Origen::Manual);
+                if es_manual {
+                    self.confirmar(ConfirmType::DeleteManual);
+                } else {
+                    self.mensaje = "Solo se pueden eliminar partidos manuales".to_string();
+                }
+            }
+            Accion::FiltrarLocal | Accion::FiltrarCompeticion => {
+                let Some(p) = self.partidos.get(self.partido_seleccionado) else {
+                    return;
+                };
+                let (nombre, buscar, categoria) = if accion == Accion::FiltrarLocal {
+                    (p.local.clone(), p.local.clone(), String::new())
+                } else {
+                    (p.competicion.clone(), String::new(), p.competicion.clone())
+                };
+                if nombre.is_empty() {
+                    return;
+                }
+                let mensaje = format!("✅ Filtro '{}' añadido", nombre);
+                self.agregar_filtro(nombre, buscar, categoria);
+                self.mensaje = mensaje;
+                self.vista_actual = Vista::Partidos;
+            }
+            _ => {}
+        }
+    }
+
+    fn en_comandos(&mut self, accion: Accion) {
+        match accion {
+            Accion::Volver => self.vista_actual = Vista::Partidos,
+            // Borrar con la línea vacía la cierra, como en vim.
+            Accion::Borrar if self.paleta.texto.is_empty() => self.vista_actual = Vista::Partidos,
+            Accion::Borrar => {
+                self.paleta.texto.pop();
+                self.paleta.candidatos.clear();
+            }
+            Accion::Escribir(c) => {
+                self.paleta.texto.push(c);
+                self.paleta.candidatos.clear();
+            }
+            Accion::Completar => self.completar_comando(),
+            Accion::Aceptar => {
+                self.vista_actual = Vista::Partidos;
+                let texto = std::mem::take(&mut self.paleta.texto);
+                self.ejecutar_comando(&texto);
+            }
+            _ => {}
+        }
+    }
+
+    fn en_buscar(&mut self, accion: Accion) {
+        match accion {
+            Accion::Volver => {
+                self.reset_busqueda();
+                self.vista_actual = Vista::Partidos;
+            }
+            Accion::Aceptar => self.vista_actual = Vista::Partidos,
+            Accion::Borrar => {
+                self.buscar_texto.pop();
+                self.aplicar_busqueda();
+            }
+            Accion::Escribir(c) => {
+                self.buscar_texto.push(c);
+                self.aplicar_busqueda();
+            }
+            _ => {}
+        }
+    }
+
+    fn confirmar(&mut self, tipo: ConfirmType) {
+        self.confirm_type = Some(tipo);
+        self.confirm_seleccion = 1;
+        self.vista_actual = Vista::Confirm;
+    }
+
+    fn en_confirmacion(&mut self, accion: Accion) {
+        match accion {
+            Accion::Volver => {
+                self.vista_actual = match self.confirm_type {
+                    Some(ConfirmType::DeleteManual) => Vista::Detalles,
+                    Some(ConfirmType::DeletePlayer) => Vista::Asistencia,
+                    _ => Vista::Filtros,
+                };
+                self.confirm_type = None;
+            }
+            Accion::Subir | Accion::Bajar => self.confirm_seleccion = 1 - self.confirm_seleccion,
+            Accion::Aceptar => {
+                if self.confirm_seleccion == 0 {
+                    match self.confirm_type {
+                        Some(ConfirmType::DeleteFilter) => self.eliminar_filtro(),
+                        Some(ConfirmType::DeleteManual) => self.eliminar_partido_manual(),
+                        Some(ConfirmType::DeletePlayer) => self.eliminar_jugador(),
+                        None => {}
+                    }
+                }
+                self.vista_actual = match self.confirm_type {
+                    Some(ConfirmType::DeletePlayer) => Vista::Asistencia,
+                    _ => Vista::Partidos,
+                };
+                self.confirm_type = None;
+            }
+            _ => {}
+        }
+    }
+
+    fn en_formulario(&mut self, accion: Accion) {
+        let campo = self.formulario.campo;
+        match accion {
+            Accion::Volver => self.vista_actual = Vista::Partidos,
+            Accion::Subir => {
+                self.formulario.campo = campo.checked_sub(1).unwrap_or(CAMPOS_FORMULARIO.len() - 1);
+            }
+            Accion::Bajar | Accion::Siguiente => {
+                self.formulario.campo = (campo + 1) % CAMPOS_FORMULARIO.len();
+            }
+            Accion::Borrar => {
+                self.formulario.campos[campo].pop();
+            }
+            Accion::Escribir(c) => self.formulario.campos[campo].push(c),
+            Accion::Aceptar => match self.guardar_formulario() {
+                Ok(()) => self.vista_actual = Vista::Partidos,
+                Err(e) => self.mensaje = format!("❌ {}", e),
+            },
+            _ => {}
+        }
+    }
+
+    fn en_nota(&mut self, accion: Accion) {
+        match accion {
+            Accion::Volver => self.vista_actual = Vista::Detalles,
+            Accion::Aceptar => {
+                self.guardar_nota();
+                self.vista_actual = Vista::Detalles;
+            }
+            Accion::Borrar => {
+                self.nota_texto.pop();
+            }
+            Accion::Escribir(c) => self.nota_texto.push(c),
+            _ => {}
+        }
+    }
+
+    fn en_nuevo_jugador(&mut self, accion: Accion) {
+        match accion {
+            Accion::Volver => self.asistencia_entrada = None,
+            Accion::Aceptar => {
+                let nombre = self.asistencia_entrada.take().unwrap_or_default();
+                self.agregar_jugador(nombre);
+            }
+            Accion::Borrar => {
+                if let Some(entrada) = self.asistencia_entrada.as_mut() {
+                    entrada.pop();
+                }
+            }
+            Accion::Escribir(c) => {
+                if let Some(entrada) = self.asistencia_entrada.as_mut() {
+                    entrada.push(c);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn en_asistencia(&mut self, accion: Accion) {
+        match accion {
+            Accion::Volver => self.vista_actual = Vista::Partidos,
+            Accion::Subir => anterior(&mut self.asistencia_jugador, 1),
+            Accion::Bajar => {
+                let total = self.plantilla().len();
+                siguiente(&mut self.asistencia_jugador, 1, total);
+            }
+            Accion::Anterior | Accion::PaginaArriba => anterior(&mut self.asistencia_partido, 1),
+            Accion::Siguiente | Accion::PaginaAbajo => {
+                let total = self.proximos_partidos().len();
+                siguiente(&mut self.asistencia_partido, 1, total);
+            }
+            Accion::Disponibilidad => self.alternar_disponibilidad(),
+            Accion::NuevoJugador => self.asistencia_entrada = Some(String::new()),
+            Accion::Eliminar if !self.plantilla().is_empty() => {
+                self.confirmar(ConfirmType::DeletePlayer);
+            }
+            Accion::Exportar => self.exportar_asistencia(),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::pruebas::AppDePrueba;
+    use fecapa::models::{ConfigPistas, Coordenadas, Filtro, Partido, Pista};
+
+    /// Una App de prueba con `n` partidos.
+    fn app(n: usize) -> AppDePrueba {
+        let mut app = AppDePrueba::nueva();
+        app.partidos = (0..n)
+            .map(|i| Partido {
+                competicion: "BCN BENJAMÍ OR P4".to_string(),
+                data: "25/10/2026".to_string(),
+                local: format!("LOCAL {}", i),
+                visitante: format!("VISITANTE {}", i),
+                ..Default::default()
+            })
+            .collect();
+        app.todos_partidos = app.partidos.clone();
+        app
+    }
+
+    #[test]
+    fn la_ayuda_vuelve_a_la_vista_desde_la_que_se_abrio() {
+        let mut app = app(3);
+        app.update(Accion::Detalles);
+        app.update(Accion::Ayuda);
+        assert_eq!(app.vista_actual, Vista::Help);
+        app.update(Accion::Volver);
+        assert_eq!(app.vista_actual, Vista::Detalles);
+    }
+
+    #[test]
+    fn la_seleccion_no_sale_de_la_lista() {
+        let mut app = app(25);
+        app.update(Accion::Subir);
+        assert_eq!(app.partido_seleccionado, 0);
+        app.update(Accion::PaginaAbajo);
+        assert_eq!(app.partido_seleccionado, TAMANO_PAGINA);
+        app.update(Accion::PaginaAbajo);
+        assert_eq!(app.partido_seleccionado, 24);
+        app.update(Accion::Bajar);
+        assert_eq!(app.partido_seleccionado, 24);
+    }
+
+    #[test]
+    fn cancelar_la_confirmacion_no_borra_el_filtro() {
+        let mut app = app(1);
+        let filtros = app.filtros.len();
+        app.update(Accion::Filtros);
+        app.update(Accion::Bajar);
+        app.update(Accion::Eliminar);
+        assert_eq!(app.vista_actual, Vista::Confirm);
+        // La selección empieza en "No".
+        app.update(Accion::Aceptar);
+        assert_eq!(app.filtros.len(), filtros);
+        assert_eq!(app.vista_actual, Vista::Partidos);
+        assert_eq!(app.confirm_type, None);
+    }
+
+    #[test]
+    fn la_busqueda_filtra_mientras_se_escribe() {
+        let mut app = app(12);
+        app.update(Accion::Buscar);
+        for c in "local 1".chars() {
+            app.update(Accion::Escribir(c));
+        }
+        assert_eq!(app.partidos.len(), 3);
+        app.update(Accion::Volver);
+        assert_eq!(app.vista_actual, Vista::Partidos);
+        assert_eq!(app.partidos.len(), 12);
+    }
+
+    #[test]
+    fn las_cuentas_repiten_el_movimiento() {
+        let mut app = app(3);
+        app.update(Accion::Cifra(2));
+        app.update(Accion::Bajar);
+        assert_eq!(app.partido_seleccionado, 2);
+        assert_eq!(app.cuenta, None);
+        app.update(Accion::Inicio);
+        assert_eq!(app.partido_seleccionado, 0);
+        app.update(Accion::Cifra(2));
+        app.update(Accion::Fin);
+        assert_eq!(app.partido_seleccionado, 1);
+    }
+
+    #[test]
+    fn solo_los_partidos_fuera_se_ordenan_por_distancia() {
+        let mut app = app(0);
+        let partido = |local: &str, visitante: &str, pista: &str| Partido {
+            local: local.to_string(),
+            visitante: visitante.to_string(),
+            pista: pista.to_string(),
+            ..Default::default()
+        };
+        let pista = |nombre: &str, lat| Pista {
+            nombre: nombre.to_string(),
+            alias: Vec::new(),
+            direccion: String::new(),
+            coordenadas: Coordenadas { lat, lon: 2.0 },
+        };
+        app.pistas = ConfigPistas {
+            casa: Some(Coordenadas {
+                lat: 41.0,
+                lon: 2.0,
+            }),
+            pistas: vec![
+                pista("LEJOS", 42.0),
+                pista("CERCA", 41.1),
+                pista("CASA", 41.0),
+            ],
+            ..Default::default()
+        };
+        app.todos_partidos = vec![
+            partido("CP VIC", "CE NOIA", "CASA"),
+            partido("CH LLOBREGAT", "CP VIC", "LEJOS"),
+            partido("CP VIC", "CP MANLLEU", "CASA"),
+            partido("CE NOIA", "CP VIC", "CERCA"),
+        ];
+        app.partidos = app.todos_partidos.clone();
+
+        app.update(Accion::OrdenDistancia);
+        assert!(
+            !app.orden_distancia,
+            "sin filtro de equipo no sabe cuáles son fuera"
+        );
+
+        app.filtros.push(Filtro {
+            nombre: "Vic".to_string(),
+            buscar: "vic".to_string(),
+            categoria: String::new(),
+            favoritos: false,
+        });
+        app.filtro_seleccionado = app.filtros.len() - 1;
+        app.aplicar_filtro();
+        app.update(Accion::OrdenDistancia);
+        let orden: Vec<&str> = app.partidos.iter().map(|p| p.local.as_str()).collect();
+        assert_eq!(orden, ["CE NOIA", "CH LLOBREGAT", "CP VIC", "CP VIC"]);
+        assert_eq!(app.partidos[2].visitante, "CE NOIA");
+    }
+
+    #[test]
+    fn caducan_segun_las_horas_configuradas() {
+        let mut app = app(1);
+        app.ajustes.horas_caducidad = 24;
+        app.actualizado = None;
+        assert!(app.antiguedad().is_none());
+        assert!(app.datos_caducados());
+
+        app.actualizado = Some(chrono::Local::now() - chrono::Duration::hours(23));
+        assert_eq!(app.antiguedad().unwrap().num_hours(), 23);
+        assert!(!app.datos_caducados());
+        app.actualizado = Some(chrono::Local::now() - chrono::Duration::hours(25));
+        assert!(app.datos_caducados());
+    }
+
+    #[test]
+    fn al_iniciar_solo_refresca_si_esta_activado_y_caducado() {
+        let mut app = app(1);
+        // Sin fuentes el refresco falla enseguida y no sale a la red.
+        app.ajustes.fuentes = Vec::new();
+        app.actualizado = Some(chrono::Local::now() - chrono::Duration::hours(48));
+        app.refrescar_si_caducados();
+        assert!(!app.scraping);
+
+        app.ajustes.refrescar_al_iniciar = true;
+        app.actualizado = Some(chrono::Local::now());
+        app.refrescar_si_caducados();
+        assert!(!app.scraping);
+
+        app.actualizado = None;
+        app.refrescar_si_caducados();
+        assert!(app.scraping);
+    }
+}
@@ -0,0 +1,305 @@
+use crate::state::App;
+use crate::vistas::Vista;
+use chrono::{Local, NaiveDate};
+use fecapa::calendario;
+use fecapa::datos::exportar_texto;
+use fecapa::models::{leer_fecha, Filtro};
+use std::collections::BTreeSet;
+
+/// Comandos de la línea `:`, con sus argumentos fijos (los demás se
+/// completan con los datos).
+const COMANDOS: [(&str, &[&str]); 6] = [
+    ("filter", &[]),
+    ("team", &[]),
+    ("goto", &["today"]),
+    ("sort", &["date", "distance"]),
+    ("export", &["ics"]),
+    ("quit", &[]),
+];
+
+const USO: &str = ":filter [filtro o competición] | :team <equipo> | :goto today|<fecha>|<número> | :sort date|distance | :export ics | :quit";
+
+/// Las opciones que empiezan por `prefijo` o, si no hay ninguna, las que lo
+/// contienen; sin distinguir mayúsculas.
+fn coincidencias(opciones: impl IntoIterator<Item = String>, prefijo: &str) -> Vec<String> {
+    let prefijo = prefijo.to_uppercase();
+    let opciones: BTreeSet<String> = opciones.into_iter().collect();
+    let empiezan: Vec<String> = opciones
+        .iter()
+        .filter(|o| o.to_uppercase().starts_with(&prefijo))
+        .cloned()
+        .collect();
+    if !empiezan.is_empty() {
+        return empiezan;
+    }
+    opciones
+        .into_iter()
+        .filter(|o| o.to_uppercase().contains(&prefijo))
+        .collect()
+}
+
+impl App {
+    /// Equipos de todos los partidos de la temporada, sin repetir.
+    fn equipos(&self) -> impl Iterator<Item = String> + '_ {
+        self.todos_partidos
+            .iter()
+            .flat_map(|p| [&p.local, &p.visitante])
+            .map(|e| e.trim().to_string())
+            .filter(|e| !e.is_empty())
+    }
+
+    /// Lo que puede ir detrás de `comando`.
+    fn argumentos(&self, comando: &str) -> Vec<String> {
+        match comando {
+            "filter" => self
+                .filtros
+                .iter()
+                .map(|f| f.nombre.clone())
+                .chain(
+                    self.todos_partidos
+                        .iter()
+                        .map(|p| p.competicion.trim().to_string()),
+                )
+                .collect(),
+            "team" => self.equipos().collect(),
+            _ => COMANDOS
+                .iter()
+                .find(|c| c.0 == comando)
+                .map(|c| c.1.iter().map(|a| a.to_string()).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Tab en la línea de comandos: completa el comando o su argumento. Si
+    /// hay varias opciones pone la primera y las siguientes pulsaciones
+    /// pasan por el resto.
+    pub fn completar_comando(&mut self) {
+        let paleta = &mut self.paleta;
+        if !paleta.candidatos.is_empty() {
+            paleta.candidato = (paleta.candidato + 1) % paleta.candidatos.len();
+            paleta.texto = paleta.candidatos[paleta.candidato].clone();
+            return;
+        }
+
+        let texto = paleta.texto.trim_start().to_string();
+        let candidatos: Vec<String> = match texto.split_once(' ') {
+            None => coincidencias(COMANDOS.iter().map(|c| c.0.to_string()), &texto)
+                .into_iter()
+                .map(|c| format!("{} ", c))
+                .collect(),
+            Some((comando, argumento)) => {
+                coincidencias(self.argumentos(comando), argumento.trim_start())
+                    .into_iter()
+                    .map(|a| format!("{} {}", comando, a))
+                    .collect()
+            }
+        };
+        match candidatos.len() {
+            0 => self.mensaje = format!("Nada que completar en «{}»", texto),
+            1 => self.paleta.texto = candidatos[0].clone(),
+            n => {
+                self.mensaje = format!(
+                    "{} opciones: {}",
+                    n,
+                    candidatos
+                        .iter()
+                        .map(|c| c.split_once(' ').map_or(c.as_str(), |(_, a)| a).trim())
+                        .collect::<Vec<_>>()
+                        .join(" | ")
+                );
+                self.paleta.texto = candidatos[0].clone();
+                self.paleta.candidatos = candidatos;
+                self.paleta.candidato = 0;
+            }
+        }
+    }
+
+    /// Ejecuta una línea de la paleta, con o sin los dos puntos delante.
+    pub fn ejecutar_comando(&mut self, linea: &str) {
+        let linea = linea.trim().trim_start_matches(':');
+        let (comando, argumento) = linea.split_once(' ').unwrap_or((linea, ""));
+        let argumento = argumento.trim();
+        match (comando, argumento) {
+            ("", _) => {}
+            ("filter", "") => self.vista_actual = Vista::Filtros,
+            ("filter", nombre) => self.filtrar_por_nombre(nombre),
+            ("team", "") => self.mensaje = "Uso: :team <equipo>".to_string(),
+            ("team", equipo) => self.filtrar_equipo(equipo),
+            ("goto", "today") => self.ir_a_fecha(Local::now().date_naive()),
+            ("goto", destino) => {
+                if let Ok(numero) = destino.parse::<usize>() {
+                    self.partido_seleccionado = numero.clamp(1, self.partidos.len().max(1)) - 1;
+                } else if let Some(fecha) = leer_fecha(destino) {
+                    self.ir_a_fecha(fecha);
+                } else {
+                    self.mensaje = "Uso: :goto today | <dd/mm/aaaa> | <número>".to_string();
+                }
+            }
+            ("sort", "date") => self.ordenar_por_fecha(),
+            ("sort", "distance") => {
+                if !self.orden_distancia {
+                    self.alternar_orden_distancia();
+                }
+            }
+            ("sort", _) => self.mensaje = "Uso: :sort date | distance".to_string(),
+            ("export", "ics") => self.exportar_ics(),
+            ("export", _) => self.mensaje = "Uso: :export ics".to_string(),
+            ("quit" | "q", _) => self.salir = true,
+            _ => self.mensaje = format!("Comando desconocido «{}». {}", comando, USO),
+        }
+    }
+
+    /// Aplica el filtro guardado con ese nombre o, si no hay ninguno, filtra
+    /// por competición sin guardarlo.
+    fn filtrar_por_nombre(&mut self, nombre: &str) {
+        if let Some(i) = self
+            .filtros
+            .iter()
+            .position(|f| f.nombre.eq_ignore_ascii_case(nombre))
+        {
+            self.filtro_seleccionado = i;
+            self.aplicar_filtro();
+            return;
+        }
+        let filtro = Filtro {
+            nombre: nombre.to_string(),
+            buscar: String::new(),
+            categoria: nombre.to_string(),
+            favoritos: false,
+        };
+        let partidos = filtro.filtrar(&self.todos_partidos, &self.anotaciones);
+        if partidos.is_empty() {
+            self.mensaje = format!("Ningún filtro ni competición «{}»", nombre);
+            return;
+        }
+        self.partidos = partidos;
+        self.partido_seleccionado = 0;
+        self.orden_distancia = false;
+        self.mensaje = format!("Competición: {} - {} partidos", nombre, self.partidos.len());
+    }
+
+    fn filtrar_equipo(&mut self, equipo: &str) {
+        let partidos: Vec<_> = self
+            .todos_partidos
+            .iter()
+            .filter(|p| p.juega(equipo))
+            .cloned()
+            .collect();
+        if partidos.is_empty() {
+            self.mensaje = format!("Ningún partido de «{}» (Tab completa)", equipo);
+            return;
+        }
+        self.partidos = partidos;
+        self.partido_seleccionado = 0;
+        self.orden_distancia = false;
+        self.mensaje = format!("Equipo: {} - {} partidos", equipo, self.partidos.len());
+    }
+
+    /// Selecciona el primer partido de `fecha` o posterior.
+    /// Selecciona el primer partido desde `fecha` por calendario, sea cual
+    /// sea el orden de la lista (por distancia, por ejemplo).
+    fn ir_a_fecha(&mut self, fecha: NaiveDate) {
+        let siguiente = self
+            .partidos
+            .iter()
+            .enumerate()
+            .filter_map(|(i, p)| Some((p.fecha().filter(|f| *f >= fecha)?, p.hora.trim(), i)))
+            .min();
+        match siguiente {
+            Some((_, _, i)) => self.partido_seleccionado = i,
+            None => self.mensaje = format!("No hay partidos desde el {}", fecha.format("%d/%m/%Y")),
+        }
+    }
+
+    fn ordenar_por_fecha(&mut self) {
+        self.partidos
+            .sort_by_cached_key(|p| (p.fecha().is_none(), p.fecha(), p.hora.trim().to_string()));
+        self.partido_seleccionado = 0;
+        self.orden_distancia = false;
+        self.mensaje = "Ordenado por fecha".to_string();
+    }
+
+    fn exportar_ics(&mut self) {
+        self.mensaje = match exportar_texto("partidos.ics", &calendario::a_ics(&self.partidos)) {
+            Ok(ruta) => format!(
+                "✅ {} partidos exportados a {}",
+                self.partidos.len(),
+                ruta.display()
+            ),
+            Err(e) => format!("❌ {}", e),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::acciones::Accion;
+    use crate::state::pruebas::AppDePrueba;
+    use crate::state::App;
+    use crate::vistas::Vista;
+    use fecapa::models::Partido;
+
+    fn app() -> AppDePrueba {
+        let mut app = AppDePrueba::nueva();
+        let partido = |data: &str, local: &str, visitante: &str| Partido {
+            competicion: "BCN BENJAMÍ OR P4".to_string(),
+            data: data.to_string(),
+            local: local.to_string(),
+            visitante: visitante.to_string(),
+            ..Default::default()
+        };
+        app.todos_partidos = vec![
+            partido("01/11/2026", "CH SANT CELONI", "CP VIC"),
+            partido("18/10/2026", "CP VIC", "CP MANLLEU"),
+            partido("25/10/2026", "CP MANLLEU", "CH SANT CELONI"),
+        ];
+        app.partidos = app.todos_partidos.clone();
+        app
+    }
+
+    fn escribir(app: &mut App, texto: &str) {
+        app.update(Accion::Comandos);
+        for c in texto.chars() {
+            app.update(Accion::Escribir(c));
+        }
+    }
+
+    #[test]
+    fn tab_completa_comandos_y_equipos() {
+        let mut app = app();
+        escribir(&mut app, "te");
+        app.update(Accion::Completar);
+        assert_eq!(app.paleta.texto, "team ");
+        app.update(Accion::Escribir('c'));
+        app.update(Accion::Escribir('p'));
+        app.update(Accion::Completar);
+        assert_eq!(app.paleta.texto, "team CP MANLLEU");
+        app.update(Accion::Completar);
+        assert_eq!(app.paleta.texto, "team CP VIC");
+        app.update(Accion::Aceptar);
+        assert_eq!(app.vista_actual, Vista::Partidos);
+        assert_eq!(app.partidos.len(), 2);
+    }
+
+    #[test]
+    fn ordena_y_va_a_una_fecha() {
+        let mut app = app();
+        app.ejecutar_comando(":sort date");
+        assert_eq!(app.partidos[0].data, "18/10/2026");
+        app.ejecutar_comando("goto 20/10/2026");
+        assert_eq!(app.partidos[app.partido_seleccionado].data, "25/10/2026");
+        app.ejecutar_comando("goto 1");
+        assert_eq!(app.partido_seleccionado, 0);
+    }
+
+    #[test]
+    fn va_a_la_fecha_mas_cercana_en_cualquier_orden() {
+        let mut app = app();
+        app.ejecutar_comando("goto 20/10/2026");
+        assert_eq!(app.partido_seleccionado, 2);
+        app.ejecutar_comando("goto 2026-10-26");
+        assert_eq!(app.partidos[app.partido_seleccionado].data, "01/11/2026");
+        app.ejecutar_comando("goto 02/11/2026");
+        assert!(app.mensaje.starts_with("No hay partidos desde"));
+    }
+}
@@ -1,22 +1,61 @@
 #![allow(non_ascii_idents)]
 
-mod commands;
-mod models;
+mod acciones;
+mod comandos;
 mod state;
+mod teclas;
 mod ui;
+mod vistas;
 
-use commands::{download_partidos, find_file_path, is_android, scrape_partidos};
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use models::Vista;
+use fecapa::datos::{self, ErrorDatos};
+use fecapa::{actas, esquema, historico, models, ratings, temporadas};
 use ratatui::{backend::CrosstermBackend, layout::Layout, Terminal};
 use state::App;
-use std::{fs, io, thread, time::Duration};
+use std::{io, time::Duration};
+use teclas::Contexto;
+use vistas::Vista;
 
 fn main() -> io::Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    let valor = |opcion: &str| {
+        args.iter()
+            .position(|a| a == opcion)
+            .and_then(|i| args.get(i + 1).cloned())
+    };
+    datos::configurar_directorios(valor("--data-dir").map(Into::into), valor("--perfil"));
+
+    if args.iter().any(|a| a == "--perfiles") {
+        imprimir_perfiles();
+        return Ok(());
+    }
+    let migrados = datos::migrar_archivos();
+
+    if std::env::args().any(|a| a == "--backtest") {
+        return terminar(imprimir_backtest());
+    }
+    if let Some(i) = args.iter().position(|a| a == "--importar-git") {
+        return terminar(importar_git(
+            args.get(i + 1).map(String::as_str).unwrap_or("."),
+        ));
+    }
+    if let Some(i) = args.iter().position(|a| a == "--restaurar") {
+        return terminar(restaurar(
+            args.get(i + 1).map(String::as_str),
+            args.get(i + 2).map(String::as_str),
+        ));
+    }
+    if let Some(i) = args.iter().position(|a| a == "--validar") {
+        return terminar(validar(args.get(i + 1).map(String::as_str)));
+    }
+    if std::env::args().any(|a| a == "--actas") {
+        return terminar(descargar_actas());
+    }
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
@@ -24,7 +63,15 @@ fn main() -> io::Result<()> {
     let mut terminal = Terminal::new(backend)?;
 
     let mut app = App::new();
-    app.recargar_datos();
+    // Con la App ya cargada, para que no lo pise el recuento de partidos.
+    if !migrados.is_empty() {
+        app.mensaje = format!(
+            "Archivos copiados a {}: {}",
+            datos::get_data_dir().display(),
+            migrados.join(", ")
+        );
+    }
+    app.refrescar_si_caducados();
 
     loop {
         terminal.draw(|f| {
@@ -36,7 +83,7 @@ fn main() -> io::Result<()> {
                 ])
                 .split(f.area());
 
-            ui::render_title(f, chunks[0]);
+            ui::render_title(f, chunks[0], &app);
 
             match app.vista_actual {
                 Vista::Partidos => {
@@ -51,273 +98,67 @@ fn main() -> io::Result<()> {
                 Vista::Buscar => {
                     ui::render_buscar(f, chunks[1], &app);
                 }
+                Vista::Comandos => {
+                    ui::render_comandos(f, chunks[1], &app);
+                }
                 Vista::Confirm => {
                     ui::render_confirm(f, chunks[1], &app);
                 }
                 Vista::Help => {
                     ui::render_help(f, chunks[1], &app);
                 }
+                Vista::Formulario => {
+                    ui::render_formulario(f, chunks[1], &app);
+                }
+                Vista::Nota => {
+                    ui::render_nota(f, chunks[1], &app);
+                }
+                Vista::Asistencia => {
+                    ui::render_asistencia(f, chunks[1], &app);
+                }
+                Vista::CaraACara => {
+                    ui::render_cara_a_cara(f, chunks[1], &app);
+                }
+                Vista::Ratings => {
+                    ui::render_ratings(f, chunks[1], &app);
+                }
+                Vista::Estadisticas => {
+                    ui::render_estadisticas(f, chunks[1], &app);
+                }
+                Vista::Clasificacion => {
+                    ui::render_clasificacion(f, chunks[1], &app);
+                }
+                Vista::Jornadas => {
+                    ui::render_jornadas(f, chunks[1], &app);
+                }
+                Vista::Jugadores => {
+                    ui::render_jugadores(f, chunks[1], &app);
+                }
+                Vista::Historial => {
+                    ui::render_historial(f, chunks[1], &app);
+                }
+                Vista::Errores => {
+                    ui::render_errores(f, chunks[1], &app);
+                }
             }
 
             ui::render_status(f, chunks[2], &app);
         })?;
 
+        app.recibir_acta();
+        app.recibir_refresco();
+
         if event::poll(Duration::from_millis(100))? {
             if let Event::Key(key) = event::read()? {
                 if key.kind == KeyEventKind::Press {
-                    match app.vista_actual {
-                        Vista::Partidos => match key.code {
-                            crossterm::event::KeyCode::Char('q') => break,
-                            crossterm::event::KeyCode::Char('?') => {
-                                app.vista_actual = Vista::Help;
-                            }
-                            crossterm::event::KeyCode::Char('r')
-                            | crossterm::event::KeyCode::Char('R') => {
-                                if !app.scraping {
-                                    app.scraping = true;
-                                    let exe_dir = std::env::current_exe()
-                                        .ok()
-                                        .and_then(|p| p.parent().map(|p| p.to_path_buf()))
-                                        .unwrap_or_default();
-
-                                    let save_path = if exe_dir.join("partidos.json").exists() {
-                                        exe_dir.join("partidos.json")
-                                    } else {
-                                        std::path::PathBuf::from("partidos.json")
-                                    };
-
-                                    if is_android() {
-                                        thread::spawn(move || {
-                                            if let Ok(partidos) = download_partidos() {
-                                                let _ = fs::write(
-                                                    &save_path,
-                                                    serde_json::to_string_pretty(&partidos)
-                                                        .unwrap_or_default(),
-                                                );
-                                            }
-                                        });
-                                    } else {
-                                        thread::spawn(move || {
-                                            if let Ok(partidos) = scrape_partidos() {
-                                                let _ = fs::write(
-                                                    &save_path,
-                                                    serde_json::to_string_pretty(&partidos)
-                                                        .unwrap_or_default(),
-                                                );
-                                            }
-                                        });
-                                    }
-                                }
-                            }
-                            crossterm::event::KeyCode::Char('f')
-                            | crossterm::event::KeyCode::Char('F') => {
-                                app.vista_actual = Vista::Filtros;
-                            }
-                            crossterm::event::KeyCode::Char('/') => {
-                                app.buscar_texto.clear();
-                                app.vista_actual = Vista::Buscar;
-                            }
-                            crossterm::event::KeyCode::Char('e')
-                            | crossterm::event::KeyCode::Enter => {
-                                app.vista_actual = Vista::Detalles;
-                            }
-                            crossterm::event::KeyCode::Up => {
-                                if app.partido_seleccionado > 0 {
-                                    app.partido_seleccionado -= 1;
-                                }
-                            }
-                            crossterm::event::KeyCode::Down => {
-                                if app.partido_seleccionado < app.partidos.len().saturating_sub(1) {
-                                    app.partido_seleccionado += 1;
-                                }
-                            }
-                            crossterm::event::KeyCode::PageDown => {
-                                let block_size = 20;
-                                let max = app.partidos.len().saturating_sub(1);
-                                app.partido_seleccionado =
-                                    (app.partido_seleccionado + block_size).min(max);
-                            }
-                            crossterm::event::KeyCode::PageUp => {
-                                let block_size = 20;
-                                app.partido_seleccionado =
-                                    app.partido_seleccionado.saturating_sub(block_size);
-                            }
-                            _ => {}
-                        },
-                        Vista::Filtros => match key.code {
-                            crossterm::event::KeyCode::Esc => {
-                                app.vista_actual = Vista::Partidos;
-                            }
-                            crossterm::event::KeyCode::Char('?') => {
-                                app.vista_actual = Vista::Help;
-                            }
-                            crossterm::event::KeyCode::Enter => {
-                                app.aplicar_filtro();
-                                app.vista_actual = Vista::Partidos;
-                            }
-                            crossterm::event::KeyCode::Char('d')
-                            | crossterm::event::KeyCode::Char('D') => {
-                                if app.filtro_seleccionado > 0 {
-                                    app.confirm_type = Some(models::ConfirmType::DeleteFilter);
-                                    app.confirm_seleccion = 1;
-                                    app.vista_actual = Vista::Confirm;
-                                }
-                            }
-                            crossterm::event::KeyCode::Up => {
-                                if app.filtro_seleccionado > 0 {
-                                    app.filtro_seleccionado -= 1;
-                                }
-                            }
-                            crossterm::event::KeyCode::Down => {
-                                if app.filtro_seleccionado < app.filtros.len().saturating_sub(1) {
-                                    app.filtro_seleccionado += 1;
-                                }
-                            }
-                            _ => {}
-                        },
-                        Vista::Detalles => match key.code {
-                            crossterm::event::KeyCode::Esc => {
-                                app.vista_actual = Vista::Partidos;
-                                app.detalle_seleccion = 0;
-                            }
-                            crossterm::event::KeyCode::Char('?') => {
-                                app.vista_actual = Vista::Help;
-                            }
-                            crossterm::event::KeyCode::Up => {
-                                if app.detalle_seleccion > 0 {
-                                    app.detalle_seleccion -= 1;
-                                }
-                            }
-                            crossterm::event::KeyCode::Down => {
-                                if app.detalle_seleccion < 5 {
-                                    app.detalle_seleccion += 1;
-                                }
-                            }
-                            crossterm::event::KeyCode::Char('a')
-                            | crossterm::event::KeyCode::Char('A') => {
-                                let (nombre, mensaje) = if let Some(p) =
-                                    app.partidos.get(app.partido_seleccionado)
-                                {
-                                    (p.local.clone(), format!("✅ Filtro '{}' añadido", p.local))
-                                } else {
-                                    (String::new(), String::new())
-                                };
-                                if !nombre.is_empty() {
-                                    app.agregar_filtro(nombre.clone(), nombre, String::new());
-                                    app.mensaje = mensaje;
-                                    app.vista_actual = Vista::Partidos;
-                                }
-                            }
-                            crossterm::event::KeyCode::Char('c')
-                            | crossterm::event::KeyCode::Char('C') => {
-                                let (nombre, mensaje) =
-                                    if let Some(p) = app.partidos.get(app.partido_seleccionado) {
-                                        (
-                                            p.competicion.clone(),
-                                            format!("✅ Filtro '{}' añadido", p.competicion),
-                                        )
-                                    } else {
-                                        (String::new(), String::new())
-                                    };
-                                if !nombre.is_empty() {
-                                    app.agregar_filtro(nombre.clone(), String::new(), nombre);
-                                    app.mensaje = mensaje;
-                                    app.vista_actual = Vista::Partidos;
-                                }
-                            }
-                            _ => {}
-                        },
-                        Vista::Buscar => match key.code {
-                            crossterm::event::KeyCode::Esc => {
-                                app.reset_busqueda();
-                                app.vista_actual = Vista::Partidos;
-                            }
-                            crossterm::event::KeyCode::Char('?') => {
-                                app.vista_actual = Vista::Help;
-                            }
-                            crossterm::event::KeyCode::Enter => {
-                                app.vista_actual = Vista::Partidos;
-                            }
-                            crossterm::event::KeyCode::Backspace => {
-                                app.buscar_texto.pop();
-                                app.aplicar_busqueda();
-                            }
-                            crossterm::event::KeyCode::Char(c) => {
-                                app.buscar_texto.push(c);
-                                app.aplicar_busqueda();
-                            }
-                            _ => {}
-                        },
-                        Vista::Confirm => match key.code {
-                            crossterm::event::KeyCode::Esc => {
-                                app.confirm_type = None;
-                                app.vista_actual = Vista::Filtros;
-                            }
-                            crossterm::event::KeyCode::Char('?') => {
-                                app.vista_actual = Vista::Help;
-                            }
-                            crossterm::event::KeyCode::Up | crossterm::event::KeyCode::Down => {
-                                if app.confirm_seleccion == 0 {
-                                    app.confirm_seleccion = 1;
-                                } else {
-                                    app.confirm_seleccion = 0;
-                                }
-                            }
-                            crossterm::event::KeyCode::Enter => {
-                                if app.confirm_seleccion == 0 {
-                                    match app.confirm_type {
-                                        Some(models::ConfirmType::DeleteFilter) => {
-                                            app.eliminar_filtro();
-                                        }
-                                        None => {}
-                                    }
-                                }
-                                app.confirm_type = None;
-                                app.vista_actual = Vista::Partidos;
-                            }
-                            _ => {}
-                        },
-                        Vista::Help => match key.code {
-                            crossterm::event::KeyCode::Esc => {
-                                app.vista_actual = Vista::Partidos;
-                            }
-                            _ => {}
-                        },
+                    if let Some(accion) = app.teclado.accion(Contexto::de(&app), &key) {
+                        app.update(accion);
                     }
                 }
             }
-
-            if app.scraping {
-                let mut waited = 0;
-                let max_wait = 30000;
-                let mut found = false;
-
-                while waited < max_wait && !found {
-                    thread::sleep(Duration::from_millis(500));
-                    waited += 500;
-
-                    let part_path = find_file_path("partidos.json");
-
-                    if let Ok(data) = fs::read_to_string(&part_path) {
-                        if let Ok(partidos) = serde_json::from_str::<Vec<models::Partido>>(&data) {
-                            if !partidos.is_empty() {
-                                app.todos_partidos = partidos.clone();
-                                app.partidos = partidos;
-                                app.aplicar_filtro();
-                                app.scraping = false;
-                                app.mensaje =
-                                    format!("✅ {} partidos guardados", app.todos_partidos.len());
-                                found = true;
-                            }
-                        }
-                    }
-                }
-
-                if !found && waited >= max_wait {
-                    app.scraping = false;
-                    app.mensaje = "❌ Timeout esperando datos".to_string();
-                }
-            }
+        }
+        if app.salir {
+            break;
         }
     }
 
@@ -331,3 +172,224 @@ fn main() -> io::Result<()> {
 
     Ok(())
 }
+
+fn imprimir_backtest() -> Result<(), ErrorDatos> {
+    // Todas las temporadas archivadas, no solo la que hay en partidos.json,
+    // y sin escribir nada.
+    let (archivo, errores) = datos::cargar_temporadas_con(&datos::cargar_partidos()?.partidos);
+    for e in errores {
+        eprintln!("⚠ {}", e);
+    }
+    let mut partidos: Vec<models::Partido> = archivo.into_values().flatten().collect();
+    partidos.extend(
+        datos::cargar_partidos_manuales()?
+            .into_iter()
+            .map(|p| models::Partido {
+                temporada: temporadas::temporada(&p).unwrap_or_default(),
+                ..p
+            }),
+    );
+    let informe = ratings::backtest(&partidos);
+
+    if informe.partidos == 0 {
+        println!("No hay partidos jugados suficientes para el backtest");
+        return Ok(());
+    }
+
+    let porcentaje = |aciertos: usize, total: usize| 100.0 * aciertos as f64 / total as f64;
+    println!(
+        "Backtest de ratings Elo ({} partidos con historial previo)",
+        informe.partidos
+    );
+    println!(
+        "  Aciertos del modelo:      {}/{} ({:.1}%)",
+        informe.aciertos,
+        informe.partidos,
+        100.0 * informe.precision()
+    );
+    println!(
+        "  Siempre gana el local:    {}/{} ({:.1}%)",
+        informe.aciertos_local,
+        informe.partidos,
+        porcentaje(informe.aciertos_local, informe.partidos)
+    );
+    println!("  Brier (0 = perfecto):     {:.3}", informe.brier);
+    println!();
+    println!("Por competición:");
+    for (competicion, total, aciertos) in &informe.por_competicion {
+        println!(
+            "  {:<30} {:>3}/{:<3} ({:.1}%)",
+            competicion,
+            aciertos,
+            total,
+            porcentaje(*aciertos, *total)
+        );
+    }
+    Ok(())
+}
+
+fn descargar_actas() -> Result<(), ErrorDatos> {
+    let partidos = datos::cargar_partidos()?.partidos;
+    let mut actas = datos::cargar_actas()?;
+    let pendientes: Vec<&models::Partido> = partidos
+        .iter()
+        .filter(|p| !p.resultado.is_empty())
+        .filter(|p| {
+            p.clave_acta()
+                .is_some_and(|clave| !actas.contains_key(clave))
+        })
+        .collect();
+
+    println!("{} actas por descargar", pendientes.len());
+    for (i, p) in pendientes.iter().enumerate() {
+        match actas::descargar_acta(p) {
+            Ok(acta) => {
+                actas.insert(p.enlace.clone(), acta);
+                println!(
+                    "[{}/{}] {} - {}",
+                    i + 1,
+                    pendientes.len(),
+                    p.local,
+                    p.visitante
+                );
+            }
+            Err(e) => println!(
+                "[{}/{}] {} - {}: {}",
+                i + 1,
+                pendientes.len(),
+                p.local,
+                p.visitante,
+                e
+            ),
+        }
+        // Guardar sobre la marcha para no perder lo descargado si se interrumpe.
+        if (i + 1) % 10 == 0 {
+            datos::guardar_actas(&actas)?;
+        }
+    }
+    datos::guardar_actas(&actas)
+}
+
+fn importar_git(ruta: &str) -> Result<(), String> {
+    let (importado, informe) =
+        historico::importar_git(std::path::Path::new(ruta), "partidos.json")?;
+    let aplazados = importado.values().filter(|h| h.aplazamientos() > 0).count();
+    let con_resultado = importado
+        .values()
+        .filter(|h| h.resultado_publicado.is_some())
+        .count();
+    let mut historico = datos::cargar_historico().map_err(|e| e.to_string())?;
+    historico::fusionar(&mut historico, importado.clone());
+    datos::guardar_historico(&historico).map_err(|e| e.to_string())?;
+
+    println!(
+        "{} commits, {} versiones de partidos.json ({} ilegibles)",
+        informe.commits, informe.versiones, informe.ilegibles
+    );
+    println!(
+        "{} partidos: {} aplazados, {} con hora de publicación del resultado",
+        importado.len(),
+        aplazados,
+        con_resultado
+    );
+    Ok(())
+}
+
+/// Termina una orden de línea de comandos. Si ha fallado muestra el error y
+/// sale con código 1 para que los scripts lo noten.
+fn terminar<E: std::fmt::Display>(resultado: Result<(), E>) -> io::Result<()> {
+    if let Err(e) = resultado {
+        eprintln!("❌ {}", e);
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn imprimir_perfiles() {
+    let dirs = datos::directorios();
+    println!("Configuración: {}", dirs.config.display());
+    println!("Datos:         {}", dirs.datos.display());
+    println!("Caché:         {}", dirs.cache.display());
+    let perfiles = datos::listar_perfiles();
+    if perfiles.is_empty() {
+        println!("No hay perfiles; se crean con --perfil <nombre>");
+    } else {
+        println!("Perfiles: {}", perfiles.join(", "));
+    }
+}
+
+/// Sin número lista las copias de un archivo; con número la restaura. Un
+/// archivo sin copias o un número que no lo es son un error de uso.
+fn restaurar(archivo: Option<&str>, numero: Option<&str>) -> Result<(), String> {
+    let uso = || {
+        format!(
+            "Uso: --restaurar <archivo> [número]\nArchivos con copias: {}",
+            datos::ARCHIVOS_CON_COPIAS.join(", ")
+        )
+    };
+    let nombre = archivo.map(|a| {
+        if a.ends_with(".json") {
+            a.to_string()
+        } else {
+            format!("{}.json", a)
+        }
+    });
+    let Some(nombre) = nombre.filter(|n| datos::ARCHIVOS_CON_COPIAS.contains(&n.as_str())) else {
+        return Err(uso());
+    };
+    let numero = match numero {
+        Some(n) => Some(n.parse::<usize>().map_err(|_| uso())?),
+        None => None,
+    };
+
+    let Some(numero) = numero else {
+        let copias = datos::listar_copias(&nombre);
+        if copias.is_empty() {
+            println!("No hay copias de {}", nombre);
+        }
+        for (numero, ruta, modificado) in copias {
+            let fecha = modificado
+                .map(|m| {
+                    chrono::DateTime::<chrono::Local>::from(m)
+                        .format("%d/%m/%Y %H:%M:%S")
+                        .to_string()
+                })
+                .unwrap_or_default();
+            println!("{}  {}  {}", numero, fecha, ruta.display());
+        }
+        return Ok(());
+    };
+
+    let ruta = datos::restaurar_copia(&nombre, numero).map_err(|e| e.to_string())?;
+    println!("✅ Copia {} restaurada en {}", numero, ruta.display());
+    println!("La versión que había es ahora la copia 1.");
+    Ok(())
+}
+
+/// Revisa un `partidos.json` (por defecto el del directorio de datos) y
+/// lista las filas sospechosas. Falla si no se aceptaría como agenda nueva.
+fn validar(ruta: Option<&str>) -> Result<(), String> {
+    let ruta = ruta
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| datos::find_file_path("partidos.json"));
+    let texto = std::fs::read_to_string(&ruta)
+        .map_err(|e| format!("Error leyendo {}: {}", ruta.display(), e))?;
+    let archivo: esquema::ArchivoPartidos = serde_json::from_str(&texto)
+        .map_err(|e| format!("Error parseando {}: {}", ruta.display(), e))?;
+
+    println!(
+        "{}: formato versión {}, {} partidos",
+        ruta.display(),
+        archivo.version,
+        archivo.partidos.len()
+    );
+    if let Some(extraido) = &archivo.extraido {
+        println!("Extraído el {} de {}", extraido, archivo.url);
+    }
+    for sospecha in esquema::validar(&archivo.partidos) {
+        println!("  {}", sospecha);
+    }
+    esquema::comprobar(&archivo).map_err(|e| format!("Se rechazaría: {}", e))?;
+    println!("✅ Se aceptaría como nueva agenda");
+    Ok(())
+}
@@ -1,24 +1,101 @@
-use crate::commands::{cargar_filtros, cargar_partidos, guardar_filtros};
-use crate::models::{ConfirmType, Filtro, Partido, Vista};
+use crate::acciones::Accion;
+use crate::teclas::{Contexto, Teclado};
+use crate::vistas::{ConfirmType, FormularioPartido, Paleta, Vista};
+use chrono::{DateTime, Local};
+use fecapa::actas;
+use fecapa::clasificacion::{self, ProyeccionEquipo};
+use fecapa::competiciones::{self, NodoArbol, Reglas};
+use fecapa::datos::{
+    apartar_archivo, cargar_actas, cargar_ajustes, cargar_anotaciones, cargar_asistencia,
+    cargar_config_competiciones, cargar_filtros, cargar_historico, cargar_partidos,
+    cargar_partidos_manuales, cargar_pistas, cargar_teclas, cargar_temporadas_con, exportar_texto,
+    find_file_path, guardar_actas, guardar_anotaciones, guardar_asistencia, guardar_filtros,
+    guardar_partidos, guardar_partidos_manuales, guardar_temporadas, ErrorDatos,
+};
+use fecapa::esquema::ArchivoPartidos;
+use fecapa::estadisticas::{self, Estadisticas};
+use fecapa::fuentes::{self, Ajustes};
+use fecapa::historico;
+use fecapa::jornadas::{self, Jornada};
+use fecapa::jugadores::{self, EstadisticaJugador, OrdenJugadores};
+use fecapa::models::{
+    Acta, Anotaciones, Asistencia, ConfigPistas, Disponibilidad, Filtro, HistoriaPartido, Origen,
+    Partido,
+};
+use fecapa::pistas;
+use fecapa::ratings::Ratings;
+use fecapa::temporadas::{self, HistorialTemporada};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
 
 pub struct App {
     pub partidos: Vec<Partido>,
     pub todos_partidos: Vec<Partido>,
+    pub partidos_manuales: Vec<Partido>,
     pub filtros: Vec<Filtro>,
     pub filtro_seleccionado: usize,
     pub partido_seleccionado: usize,
     pub vista_actual: Vista,
+    /// Vista a la que vuelve la ayuda.
+    pub vista_anterior: Vista,
+    /// El usuario ha pedido salir.
+    pub salir: bool,
+    /// Repeticiones tecleadas para la próxima acción (el 5 de `5j`).
+    pub cuenta: Option<usize>,
+    pub paleta: Paleta,
     pub mensaje: String,
     pub scraping: bool,
     pub buscar_texto: String,
     pub confirm_type: Option<ConfirmType>,
     pub confirm_seleccion: usize,
     pub detalle_seleccion: usize,
+    pub formulario: FormularioPartido,
+    pub anotaciones: Anotaciones,
+    pub nota_texto: String,
+    pub asistencia: Asistencia,
+    pub asistencia_partido: usize,
+    pub asistencia_jugador: usize,
+    pub asistencia_entrada: Option<String>,
+    pub pistas: ConfigPistas,
+    pub orden_distancia: bool,
+    pub ratings: Ratings,
+    pub ratings_competicion: usize,
+    pub estadisticas: Option<Estadisticas>,
+    pub clasificacion_competicion: usize,
+    pub clasificacion_pestana: usize,
+    pub proyeccion: Vec<ProyeccionEquipo>,
+    pub reglas_competicion: Reglas,
+    pub arbol_activo: bool,
+    pub arbol_seleccionado: usize,
+    pub arbol_expandidos: HashSet<String>,
+    pub jornadas: Vec<Jornada>,
+    pub jornada_actual: usize,
+    pub actas: BTreeMap<String, Acta>,
+    pub acta_pendiente: Option<Receiver<(String, Result<Acta, String>)>>,
+    pub jugadores: Vec<EstadisticaJugador>,
+    pub jugadores_competicion: usize,
+    pub jugadores_equipo: bool,
+    pub jugadores_orden: OrdenJugadores,
+    pub jugadores_seleccionado: usize,
+    pub archivo: BTreeMap<String, Vec<Partido>>,
+    pub temporada: String,
+    pub historial: Vec<HistorialTemporada>,
+    pub historico: BTreeMap<String, HistoriaPartido>,
+    pub ajustes: Ajustes,
+    pub teclado: Teclado,
+    pub refresco_pendiente: Option<Receiver<fuentes::Obtenidos>>,
+    /// Cuándo se extrajeron o descargaron los partidos y de qué fuente.
+    pub actualizado: Option<DateTime<Local>>,
+    pub fuente_datos: String,
+    pub errores: Vec<ErrorDatos>,
+    pub error_seleccionado: usize,
 }
 
 impl App {
     pub fn new() -> Self {
-        let mut filtros = cargar_filtros();
+        let mut errores = Vec::new();
+        let mut filtros = o_defecto(cargar_filtros(), &mut errores);
 
         if filtros.is_empty() || !filtros.iter().any(|f| f.nombre == "Todos") {
             filtros.insert(
@@ -27,54 +104,152 @@ impl App {
                     nombre: "Todos".to_string(),
                     buscar: "".to_string(),
                     categoria: "".to_string(),
+                    favoritos: false,
                 },
             );
         }
 
-        let partidos = cargar_partidos();
+        if !filtros.iter().any(|f| f.favoritos) {
+            filtros.insert(
+                1,
+                Filtro {
+                    nombre: "Favorits".to_string(),
+                    buscar: "".to_string(),
+                    categoria: "".to_string(),
+                    favoritos: true,
+                },
+            );
+        }
+
+        let partidos_manuales = o_defecto(cargar_partidos_manuales(), &mut errores);
+        let datos = o_defecto(cargar_partidos(), &mut errores);
+        // Abrir la aplicación no escribe nada: lo descargado se archiva al
+        // refrescar y las claves migradas se guardan con el siguiente cambio.
+        let (archivo, errores_archivo) = cargar_temporadas_con(&datos.partidos);
+        errores.extend(errores_archivo);
+        let temporada = temporadas::temporadas(&archivo, &partidos_manuales)
+            .pop()
+            .unwrap_or_default();
+        let partidos = temporadas::partidos_de(&archivo, &partidos_manuales, &temporada);
         let num_partidos = partidos.len();
+        let ratings = Ratings::calcular(&partidos);
+        let (reglas_competicion, errores_reglas) =
+            Reglas::nuevas(&o_defecto(cargar_config_competiciones(), &mut errores));
+        if !errores_reglas.is_empty() {
+            errores.push(ErrorDatos::Contenido {
+                ruta: find_file_path("competiciones.json"),
+                problemas: errores_reglas,
+            });
+        }
+        let mut anotaciones = o_defecto(cargar_anotaciones(), &mut errores);
+        let mut asistencia = o_defecto(cargar_asistencia(), &mut errores);
+        temporadas::migrar_anotaciones(
+            archivo.values().flatten().chain(&partidos_manuales),
+            &mut anotaciones,
+            &mut asistencia,
+        );
+        let pistas = o_defecto(cargar_pistas(), &mut errores);
+        let actas = o_defecto(cargar_actas(), &mut errores);
+        let historico = o_defecto(cargar_historico(), &mut errores);
+        let ajustes = o_defecto(cargar_ajustes(), &mut errores);
+        let (teclado, problemas_teclas) =
+            Teclado::con_ajustes(&o_defecto(cargar_teclas(), &mut errores));
+        if !problemas_teclas.is_empty() {
+            errores.push(ErrorDatos::Contenido {
+                ruta: find_file_path("teclas.json"),
+                problemas: problemas_teclas,
+            });
+        }
+        let mensaje = match errores.first() {
+            Some(error) => match teclado.tecla(Contexto::Partidos, Accion::Errores) {
+                Some(tecla) => format!("❌ {} ({} para ver)", error, tecla),
+                None => format!("❌ {}", error),
+            },
+            None => format!("{} partidos cargados", num_partidos),
+        };
 
-        Self {
+        let mut app = Self {
             partidos: partidos.clone(),
             todos_partidos: partidos,
+            partidos_manuales,
             filtros,
             filtro_seleccionado: 0,
             partido_seleccionado: 0,
             vista_actual: Vista::Partidos,
-            mensaje: format!("{} partidos cargados", num_partidos),
+            vista_anterior: Vista::Partidos,
+            salir: false,
+            cuenta: None,
+            paleta: Paleta::default(),
+            mensaje: String::new(),
             scraping: false,
             buscar_texto: String::new(),
             confirm_type: None,
             confirm_seleccion: 1,
             detalle_seleccion: 0,
-        }
+            formulario: FormularioPartido::default(),
+            anotaciones,
+            nota_texto: String::new(),
+            asistencia,
+            asistencia_partido: 0,
+            asistencia_jugador: 0,
+            asistencia_entrada: None,
+            pistas,
+            orden_distancia: false,
+            ratings,
+            ratings_competicion: 0,
+            estadisticas: None,
+            clasificacion_competicion: 0,
+            clasificacion_pestana: 0,
+            proyeccion: Vec::new(),
+            reglas_competicion,
+            arbol_activo: false,
+            arbol_seleccionado: 0,
+            arbol_expandidos: HashSet::new(),
+            jornadas: Vec::new(),
+            jornada_actual: 0,
+            actas,
+            acta_pendiente: None,
+            jugadores: Vec::new(),
+            jugadores_competicion: 0,
+            jugadores_equipo: false,
+            jugadores_orden: OrdenJugadores::default(),
+            jugadores_seleccionado: 0,
+            archivo,
+            temporada,
+            historial: Vec::new(),
+            historico,
+            ajustes,
+            teclado,
+            refresco_pendiente: None,
+            actualizado: datos.actualizado(),
+            fuente_datos: datos.fuente,
+            errores,
+            error_seleccionado: 0,
+        };
+        // El filtro seleccionado sin pisar el aviso de la carga.
+        app.aplicar_filtro();
+        app.mensaje = mensaje;
+        app
     }
 
     pub fn aplicar_filtro(&mut self) {
         let filtro = &self.filtros[self.filtro_seleccionado];
+        self.orden_distancia = false;
 
-        if filtro.buscar.is_empty() {
+        if filtro.favoritos {
+            self.partidos = filtro.filtrar(&self.todos_partidos, &self.anotaciones);
+            self.mensaje = format!("Favorits: {} partidos", self.partidos.len());
+            self.partido_seleccionado = 0;
+            return;
+        }
+
+        if filtro.buscar.is_empty() && filtro.categoria.is_empty() {
             self.partidos = self.todos_partidos.clone();
             self.mensaje = format!("Mostrando todos los partidos: {}", self.partidos.len());
             return;
         }
 
-        let buscar = filtro.buscar.to_uppercase();
-        let categoria = filtro.categoria.to_uppercase();
-
-        self.partidos = self
-            .todos_partidos
-            .iter()
-            .filter(|p| {
-                let texto = format!("{} {} {} {}", p.competicion, p.local, p.visitante, p.pista)
-                    .to_uppercase();
-                let cumple_buscar = texto.contains(&buscar);
-                let cumple_cat =
-                    categoria.is_empty() || p.competicion.to_uppercase().contains(&categoria);
-                cumple_buscar && cumple_cat
-            })
-            .cloned()
-            .collect();
+        self.partidos = filtro.filtrar(&self.todos_partidos, &self.anotaciones);
 
         self.mensaje = format!(
             "Filtro: {} - {} partidos",
@@ -84,8 +259,151 @@ impl App {
         self.partido_seleccionado = 0;
     }
 
-    pub fn recargar_datos(&mut self) {
-        let partidos = cargar_partidos();
+    /// Anota un error de archivo (uno por archivo) y lo muestra.
+    pub fn registrar_error(&mut self, error: ErrorDatos) {
+        self.mensaje = format!("❌ {}", error);
+        self.errores.retain(|e| e.ruta() != error.ruta());
+        self.errores.push(error);
+    }
+
+    fn informar(&mut self, resultado: Result<(), ErrorDatos>) {
+        if let Err(e) = resultado {
+            self.registrar_error(e);
+        }
+    }
+
+    pub fn abrir_errores(&mut self) {
+        if self.errores.is_empty() {
+            self.mensaje = "No hay errores en los archivos".to_string();
+            return;
+        }
+        self.error_seleccionado = 0;
+        self.vista_actual = Vista::Errores;
+    }
+
+    /// Aparta el archivo con errores (queda una copia) para poder volver a
+    /// guardarlo con los datos actuales.
+    pub fn apartar_archivo_error(&mut self) {
+        let Some(error) = self.errores.get(self.error_seleccionado).cloned() else {
+            return;
+        };
+        if !matches!(
+            error,
+            ErrorDatos::Formato { .. } | ErrorDatos::Bloqueado { .. }
+        ) {
+            return;
+        }
+        match apartar_archivo(error.ruta()) {
+            Ok(copia) => {
+                self.errores.remove(self.error_seleccionado);
+                self.error_seleccionado = self
+                    .error_seleccionado
+                    .min(self.errores.len().saturating_sub(1));
+                self.mensaje = format!("Copia guardada en {}", copia.display());
+                if self.errores.is_empty() {
+                    self.vista_actual = Vista::Partidos;
+                }
+            }
+            Err(e) => self.mensaje = format!("❌ {}", e),
+        }
+    }
+
+    /// Archiva los partidos de la FECAPA recién descargados y vuelve a
+    /// mostrar la temporada seleccionada con los manuales, que nunca se
+    /// pierden al refrescar. Si se estaba viendo la temporada más reciente,
+    /// se pasa a la nueva cuando la web cambia de temporada.
+    pub fn establecer_partidos(&mut self, mut partidos: Vec<Partido>) {
+        partidos.retain(|p| p.origen == Origen::Fecapa);
+        let en_ultima = self
+            .temporadas()
+            .last()
+            .is_none_or(|t| *t == self.temporada);
+        temporadas::incorporar(&mut self.archivo, &partidos);
+        for error in guardar_temporadas(&self.archivo) {
+            self.registrar_error(error);
+        }
+        if en_ultima {
+            self.temporada = self.temporadas().pop().unwrap_or_default();
+        }
+        self.aplicar_temporada();
+    }
+
+    /// Pide los partidos a las fuentes de `ajustes.json` en segundo plano;
+    /// `recibir_refresco` los recoge.
+    pub fn refrescar(&mut self) {
+        if self.scraping {
+            return;
+        }
+        let ajustes = self.ajustes.clone();
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(fuentes::obtener(&ajustes));
+        });
+        self.refresco_pendiente = Some(rx);
+        self.scraping = true;
+    }
+
+    pub fn recibir_refresco(&mut self) {
+        let Some(rx) = &self.refresco_pendiente else {
+            return;
+        };
+        let Ok(resultado) = rx.try_recv() else {
+            return;
+        };
+        self.refresco_pendiente = None;
+        self.scraping = false;
+        match resultado {
+            Ok(datos) => {
+                if let Err(e) = guardar_partidos(&datos) {
+                    self.registrar_error(e);
+                    return;
+                }
+                self.anotar_procedencia(&datos);
+                let num_partidos = datos.partidos.len();
+                self.establecer_partidos(datos.partidos);
+                self.mensaje = format!(
+                    "✅ {} partidos guardados ({})",
+                    num_partidos, self.fuente_datos
+                );
+            }
+            Err(e) => self.mensaje = format!("❌ {}", e),
+        }
+    }
+
+    fn anotar_procedencia(&mut self, datos: &ArchivoPartidos) {
+        self.actualizado = datos.actualizado();
+        self.fuente_datos = datos.fuente.clone();
+    }
+
+    /// Tiempo desde que se extrajeron o descargaron los partidos.
+    pub fn antiguedad(&self) -> Option<chrono::Duration> {
+        self.actualizado.map(|fecha| Local::now() - fecha)
+    }
+
+    /// Los partidos son más viejos de lo que marca `horas_caducidad` (o no
+    /// se sabe de cuándo son).
+    pub fn datos_caducados(&self) -> bool {
+        self.antiguedad()
+            .is_none_or(|d| d > chrono::Duration::hours(self.ajustes.horas_caducidad))
+    }
+
+    /// Al arrancar, refresca en segundo plano si así está configurado y los
+    /// partidos están caducados.
+    pub fn refrescar_si_caducados(&mut self) {
+        if self.ajustes.refrescar_al_iniciar && self.datos_caducados() {
+            self.refrescar();
+        }
+    }
+
+    pub fn temporadas(&self) -> Vec<String> {
+        temporadas::temporadas(&self.archivo, &self.partidos_manuales)
+    }
+
+    /// Limita todas las vistas a los partidos de la temporada seleccionada.
+    pub fn aplicar_temporada(&mut self) {
+        let partidos =
+            temporadas::partidos_de(&self.archivo, &self.partidos_manuales, &self.temporada);
+        self.ratings = Ratings::calcular(&partidos);
         self.todos_partidos = partidos.clone();
         self.partidos = partidos;
         if !self.filtros.is_empty() {
@@ -93,6 +411,52 @@ impl App {
         }
     }
 
+    /// Partidos de todas las temporadas archivadas más los manuales.
+    pub fn historial_partidos(&self) -> impl Iterator<Item = &Partido> {
+        self.archivo
+            .values()
+            .flatten()
+            .chain(&self.partidos_manuales)
+    }
+
+    /// Pasa a la temporada anterior; desde la más antigua vuelve a la actual.
+    pub fn cambiar_temporada(&mut self) {
+        let temporadas = self.temporadas();
+        if temporadas.len() < 2 {
+            self.mensaje = "No hay temporadas anteriores archivadas".to_string();
+            return;
+        }
+        let i = temporadas
+            .iter()
+            .position(|t| *t == self.temporada)
+            .unwrap_or(0);
+        self.temporada = if i == 0 {
+            temporadas[temporadas.len() - 1].clone()
+        } else {
+            temporadas[i - 1].clone()
+        };
+        self.partido_seleccionado = 0;
+        self.aplicar_temporada();
+        self.mensaje = format!(
+            "Temporada {}: {} partidos",
+            self.temporada,
+            self.todos_partidos.len()
+        );
+    }
+
+    pub fn abrir_historial(&mut self) {
+        let Some(filtro) = self.filtro_equipo().cloned() else {
+            self.mensaje = "Selecciona primero un filtro de equipo (F)".to_string();
+            return;
+        };
+        self.historial = temporadas::historial(&self.archivo, &filtro.buscar);
+        if self.historial.is_empty() {
+            self.mensaje = format!("{}: sin partidos archivados", filtro.nombre);
+            return;
+        }
+        self.vista_actual = Vista::Historial;
+    }
+
     pub fn aplicar_busqueda(&mut self) {
         if self.buscar_texto.is_empty() {
             self.partidos = self.todos_partidos.clone();
@@ -100,19 +464,10 @@ impl App {
             return;
         }
 
-        let buscar = self.buscar_texto.to_uppercase();
-
         self.partidos = self
             .todos_partidos
             .iter()
-            .filter(|p| {
-                let texto = format!(
-                    "{} {} {} {} {}",
-                    p.competicion, p.local, p.visitante, p.pista, p.resultado
-                )
-                .to_uppercase();
-                texto.contains(&buscar)
-            })
+            .filter(|p| p.contiene(&self.buscar_texto))
             .cloned()
             .collect();
 
@@ -138,8 +493,8 @@ impl App {
         if self.filtro_seleccionado >= self.filtros.len() {
             self.filtro_seleccionado = self.filtros.len().saturating_sub(1);
         }
-        guardar_filtros(&self.filtros);
         self.aplicar_filtro();
+        self.informar(guardar_filtros(&self.filtros));
     }
 
     pub fn agregar_filtro(&mut self, nombre: String, buscar: String, categoria: String) {
@@ -147,10 +502,662 @@ impl App {
             nombre,
             buscar,
             categoria,
+            favoritos: false,
         };
         self.filtros.push(nuevo_filtro);
-        guardar_filtros(&self.filtros);
         self.filtro_seleccionado = self.filtros.len() - 1;
         self.aplicar_filtro();
+        self.informar(guardar_filtros(&self.filtros));
+    }
+
+    pub fn nuevo_partido_manual(&mut self) {
+        self.formulario = FormularioPartido::nuevo();
+        self.vista_actual = Vista::Formulario;
+    }
+
+    pub fn editar_partido_manual(&mut self) -> bool {
+        let indice = self
+            .partidos
+            .get(self.partido_seleccionado)
+            .and_then(|p| self.partidos_manuales.iter().position(|m| m == p));
+        match indice {
+            Some(i) => {
+                self.formulario = FormularioPartido::desde_partido(&self.partidos_manuales[i], i);
+                self.vista_actual = Vista::Formulario;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn guardar_formulario(&mut self) -> Result<(), String> {
+        let partido = self.formulario.a_partido();
+        if partido.local.is_empty() || partido.visitante.is_empty() {
+            return Err("Falta el equipo local o visitante".to_string());
+        }
+        if !partido.data.is_empty() && partido.fecha().is_none() {
+            return Err(format!("Fecha no válida: {} (dd/mm/aaaa)", partido.data));
+        }
+
+        match self.formulario.editando {
+            Some(i) if i < self.partidos_manuales.len() => self.partidos_manuales[i] = partido,
+            _ => self.partidos_manuales.push(partido),
+        }
+        let guardado = guardar_partidos_manuales(&self.partidos_manuales);
+        self.aplicar_temporada();
+        if let Err(e) = guardado {
+            self.registrar_error(e.clone());
+            return Err(e.to_string());
+        }
+        self.mensaje = format!(
+            "✅ Partido manual guardado ({} en total)",
+            self.partidos_manuales.len()
+        );
+        Ok(())
+    }
+
+    pub fn eliminar_partido_manual(&mut self) {
+        let indice = self
+            .partidos
+            .get(self.partido_seleccionado)
+            .and_then(|p| self.partidos_manuales.iter().position(|m| m == p));
+        if let Some(i) = indice {
+            self.partidos_manuales.remove(i);
+            let guardado = guardar_partidos_manuales(&self.partidos_manuales);
+            self.aplicar_temporada();
+            self.mensaje = "🗑 Partido manual eliminado".to_string();
+            self.informar(guardado);
+        }
+    }
+
+    pub fn alternar_favorito(&mut self) {
+        let Some(clave) = self
+            .partidos
+            .get(self.partido_seleccionado)
+            .map(|p| p.clave_temporada())
+        else {
+            return;
+        };
+        if let Some(i) = self.anotaciones.favoritos.iter().position(|c| *c == clave) {
+            self.anotaciones.favoritos.remove(i);
+            self.mensaje = "☆ Quitado de favoritos".to_string();
+        } else {
+            self.anotaciones.favoritos.push(clave);
+            self.mensaje = "★ Añadido a favoritos".to_string();
+        }
+        self.informar(guardar_anotaciones(&self.anotaciones));
+    }
+
+    pub fn editar_nota(&mut self) {
+        if let Some(p) = self.partidos.get(self.partido_seleccionado) {
+            self.nota_texto = self.anotaciones.nota(p).cloned().unwrap_or_default();
+            self.vista_actual = Vista::Nota;
+        }
+    }
+
+    pub fn guardar_nota(&mut self) {
+        let Some(clave) = self
+            .partidos
+            .get(self.partido_seleccionado)
+            .map(|p| p.clave_temporada())
+        else {
+            return;
+        };
+        let texto = self.nota_texto.trim().to_string();
+        if texto.is_empty() {
+            self.anotaciones.notas.remove(&clave);
+            self.mensaje = "Nota eliminada".to_string();
+        } else {
+            self.anotaciones.notas.insert(clave, texto);
+            self.mensaje = "✅ Nota guardada".to_string();
+        }
+        self.informar(guardar_anotaciones(&self.anotaciones));
+    }
+
+    /// Filtro activo cuando apunta a un equipo (no "Todos" ni "Favorits").
+    /// La plantilla de disponibilidad se guarda con el nombre del filtro.
+    pub fn filtro_equipo(&self) -> Option<&Filtro> {
+        self.filtros
+            .get(self.filtro_seleccionado)
+            .filter(|f| !f.favoritos && !f.buscar.is_empty())
+    }
+
+    pub fn plantilla(&self) -> &[String] {
+        self.filtro_equipo()
+            .and_then(|f| self.asistencia.plantillas.get(&f.nombre))
+            .map(|v| v.as_slice())
+            .unwrap_or(&[])
+    }
+
+    pub fn proximos_partidos(&self) -> Vec<&Partido> {
+        let hoy = chrono::Local::now().date_naive();
+        self.partidos
+            .iter()
+            .filter(|p| p.resultado.is_empty() && p.fecha().is_none_or(|d| d >= hoy))
+            .collect()
+    }
+
+    pub fn abrir_asistencia(&mut self) {
+        if self.filtro_equipo().is_none() {
+            self.mensaje = "Selecciona primero un filtro de equipo (F)".to_string();
+            return;
+        }
+        self.asistencia_partido = 0;
+        self.asistencia_jugador = 0;
+        self.asistencia_entrada = None;
+        self.vista_actual = Vista::Asistencia;
+    }
+
+    pub fn alternar_disponibilidad(&mut self) {
+        let Some(jugador) = self.plantilla().get(self.asistencia_jugador).cloned() else {
+            return;
+        };
+        let Some(clave) = self
+            .proximos_partidos()
+            .get(self.asistencia_partido)
+            .map(|p| p.clave_temporada())
+        else {
+            return;
+        };
+        let estados = self
+            .asistencia
+            .disponibilidad
+            .entry(clave.clone())
+            .or_default();
+        match Disponibilidad::siguiente(estados.get(&jugador).copied()) {
+            Some(estado) => {
+                estados.insert(jugador, estado);
+            }
+            None => {
+                estados.remove(&jugador);
+            }
+        }
+        if estados.is_empty() {
+            self.asistencia.disponibilidad.remove(&clave);
+        }
+        self.informar(guardar_asistencia(&self.asistencia));
+    }
+
+    pub fn agregar_jugador(&mut self, nombre: String) {
+        let nombre = nombre.trim().to_string();
+        let Some(equipo) = self.filtro_equipo().map(|f| f.nombre.clone()) else {
+            return;
+        };
+        if nombre.is_empty() {
+            return;
+        }
+        let plantilla = self.asistencia.plantillas.entry(equipo).or_default();
+        if plantilla.contains(&nombre) {
+            self.mensaje = format!("{} ya está en la plantilla", nombre);
+            return;
+        }
+        plantilla.push(nombre);
+        self.asistencia_jugador = plantilla.len() - 1;
+        self.informar(guardar_asistencia(&self.asistencia));
+    }
+
+    pub fn eliminar_jugador(&mut self) {
+        let Some(equipo) = self.filtro_equipo().map(|f| f.nombre.clone()) else {
+            return;
+        };
+        if let Some(plantilla) = self.asistencia.plantillas.get_mut(&equipo) {
+            if self.asistencia_jugador < plantilla.len() {
+                let jugador = plantilla.remove(self.asistencia_jugador);
+                for estados in self.asistencia.disponibilidad.values_mut() {
+                    estados.remove(&jugador);
+                }
+                self.asistencia.disponibilidad.retain(|_, m| !m.is_empty());
+                self.asistencia_jugador = self
+                    .asistencia_jugador
+                    .min(plantilla.len().saturating_sub(1));
+                self.informar(guardar_asistencia(&self.asistencia));
+                self.mensaje = format!("🗑 {} eliminado de la plantilla", jugador);
+            }
+        }
+    }
+
+    pub fn texto_asistencia(&self) -> Option<String> {
+        let equipo = self.filtro_equipo()?;
+        let p = *self.proximos_partidos().get(self.asistencia_partido)?;
+        let plantilla = self.plantilla();
+
+        let mut texto = format!("🏒 {}\n{} vs {}\n", equipo.nombre, p.local, p.visitante);
+        texto.push_str(&format!("📅 {} {} - {}\n", p.data, p.hora, p.pista));
+        let grupos = [
+            (Some(Disponibilidad::Si), "✅ Sí"),
+            (Some(Disponibilidad::No), "❌ No"),
+            (Some(Disponibilidad::Quiza), "❓ Quizá"),
+            (None, "⏳ Sin respuesta"),
+        ];
+        for (estado, etiqueta) in grupos {
+            let jugadores: Vec<&str> = plantilla
+                .iter()
+                .filter(|j| self.asistencia.estado(p, j) == estado)
+                .map(|j| j.as_str())
+                .collect();
+            texto.push_str(&format!(
+                "\n{} ({}): {}",
+                etiqueta,
+                jugadores.len(),
+                jugadores.join(", ")
+            ));
+        }
+        texto.push('\n');
+        Some(texto)
+    }
+
+    pub fn exportar_asistencia(&mut self) {
+        let Some(texto) = self.texto_asistencia() else {
+            self.mensaje = "No hay ningún partido pendiente seleccionado".to_string();
+            return;
+        };
+        self.mensaje = match exportar_texto("asistencia.txt", &texto) {
+            Ok(ruta) => format!("✅ Convocatoria exportada a {}", ruta.display()),
+            Err(e) => format!("❌ {}", e),
+        };
+    }
+
+    pub fn alternar_orden_distancia(&mut self) {
+        if self.orden_distancia {
+            self.aplicar_filtro();
+            return;
+        }
+        if self.pistas.casa.is_none() {
+            self.mensaje =
+                "Configura \"casa\" en pistas.json para ordenar por distancia".to_string();
+            return;
+        }
+        let Some(equipo) = self
+            .filtro_equipo()
+            .and_then(|f| estadisticas::equipo_del_filtro(&self.partidos, &f.buscar))
+        else {
+            self.mensaje =
+                "Selecciona un filtro de equipo (F) para ordenar sus partidos fuera".to_string();
+            return;
+        };
+        // Los de fuera del más cercano al más lejano; los de casa y los de
+        // pistas sin registrar, detrás y en el orden que tenían.
+        let config = &self.pistas;
+        let visitante = equipo.to_uppercase();
+        self.partidos.sort_by_cached_key(|p| {
+            let distancia = (p.visitante.trim().to_uppercase() == visitante)
+                .then(|| pistas::trayecto(config, p))
+                .flatten()
+                .map(|t| t.distancia_km);
+            (
+                distancia.is_none(),
+                distancia.map(|d| (d * 1000.0) as u64).unwrap_or(0),
+            )
+        });
+        self.partido_seleccionado = 0;
+        self.orden_distancia = true;
+        self.mensaje = format!("Partidos fuera de {} por distancia desde casa", equipo);
+    }
+
+    pub fn abrir_ratings(&mut self) {
+        let competiciones = self.ratings.competiciones();
+        if competiciones.is_empty() {
+            self.mensaje = "No hay partidos jugados para calcular ratings".to_string();
+            return;
+        }
+        self.ratings_competicion = self
+            .partidos
+            .get(self.partido_seleccionado)
+            .and_then(|p| competiciones.iter().position(|c| **c == p.competicion))
+            .unwrap_or(0);
+        self.vista_actual = Vista::Ratings;
+    }
+
+    pub fn abrir_estadisticas(&mut self) {
+        let equipo = self
+            .filtro_equipo()
+            .and_then(|f| estadisticas::equipo_del_filtro(&self.partidos, &f.buscar));
+        let Some(equipo) = equipo else {
+            self.mensaje = "Selecciona primero un filtro de equipo (F)".to_string();
+            return;
+        };
+        self.estadisticas = Some(estadisticas::calcular(
+            &self.partidos,
+            &self.todos_partidos,
+            &equipo,
+            3,
+        ));
+        self.vista_actual = Vista::Estadisticas;
+    }
+
+    pub fn competicion_clasificacion(&self) -> Option<String> {
+        clasificacion::competiciones(&self.todos_partidos)
+            .into_iter()
+            .nth(self.clasificacion_competicion)
+    }
+
+    pub fn abrir_clasificacion(&mut self) {
+        let competiciones = clasificacion::competiciones(&self.todos_partidos);
+        if competiciones.is_empty() {
+            self.mensaje = "No hay partidos cargados".to_string();
+            return;
+        }
+        self.clasificacion_competicion = self
+            .partidos
+            .get(self.partido_seleccionado)
+            .and_then(|p| competiciones.iter().position(|c| *c == p.competicion))
+            .unwrap_or(0);
+        self.clasificacion_pestana = 0;
+        self.proyeccion.clear();
+        self.vista_actual = Vista::Clasificacion;
+    }
+
+    pub fn cambiar_competicion_clasificacion(&mut self, siguiente: bool) {
+        let total = clasificacion::competiciones(&self.todos_partidos).len();
+        if siguiente && self.clasificacion_competicion + 1 < total {
+            self.clasificacion_competicion += 1;
+        } else if !siguiente && self.clasificacion_competicion > 0 {
+            self.clasificacion_competicion -= 1;
+        } else {
+            return;
+        }
+        self.calcular_proyeccion();
+    }
+
+    pub fn alternar_pestana_clasificacion(&mut self) {
+        self.clasificacion_pestana = (self.clasificacion_pestana + 1) % 2;
+        self.calcular_proyeccion();
+    }
+
+    /// La simulación es costosa: solo se recalcula al entrar en la pestaña
+    /// de proyección o al cambiar de competición.
+    fn calcular_proyeccion(&mut self) {
+        self.proyeccion.clear();
+        if self.clasificacion_pestana != 1 {
+            return;
+        }
+        if let Some(competicion) = self.competicion_clasificacion() {
+            self.proyeccion =
+                clasificacion::proyeccion(&self.todos_partidos, &competicion, &self.ratings);
+        }
+    }
+
+    pub fn nodos_arbol(&self) -> Vec<NodoArbol> {
+        competiciones::arbol(
+            &clasificacion::competiciones(&self.todos_partidos),
+            &self.reglas_competicion,
+            &self.arbol_expandidos,
+        )
+    }
+
+    /// Despliega o pliega una rama; en una hoja muestra sus partidos.
+    pub fn activar_nodo_arbol(&mut self) {
+        let Some(nodo) = self.nodos_arbol().into_iter().nth(self.arbol_seleccionado) else {
+            return;
+        };
+        if !nodo.es_hoja() {
+            if !self.arbol_expandidos.remove(&nodo.ruta) {
+                self.arbol_expandidos.insert(nodo.ruta);
+            }
+            return;
+        }
+        self.partidos = self
+            .todos_partidos
+            .iter()
+            .filter(|p| nodo.competiciones.contains(&p.competicion))
+            .cloned()
+            .collect();
+        self.partido_seleccionado = 0;
+        self.orden_distancia = false;
+        self.mensaje = format!(
+            "Competición: {} - {} partidos",
+            nodo.competiciones.join(", "),
+            self.partidos.len()
+        );
+        self.vista_actual = Vista::Partidos;
+    }
+
+    pub fn plegar_nodo_arbol(&mut self) {
+        let nodos = self.nodos_arbol();
+        let Some(nodo) = nodos.get(self.arbol_seleccionado) else {
+            return;
+        };
+        if self.arbol_expandidos.remove(&nodo.ruta) {
+            return;
+        }
+        // Sin nada que plegar, sube al nodo padre.
+        if let Some(padre) = nodos[..self.arbol_seleccionado]
+            .iter()
+            .rposition(|n| n.profundidad < nodo.profundidad)
+        {
+            self.arbol_seleccionado = padre;
+        }
+    }
+
+    pub fn guardar_nodo_como_filtro(&mut self) {
+        let Some(nodo) = self.nodos_arbol().into_iter().nth(self.arbol_seleccionado) else {
+            return;
+        };
+        match nodo.competiciones.as_slice() {
+            [competicion] if nodo.es_hoja() => {
+                let competicion = competicion.clone();
+                self.agregar_filtro(competicion.clone(), String::new(), competicion.clone());
+                self.arbol_activo = false;
+                self.mensaje = format!("✅ Filtro '{}' añadido", competicion);
+            }
+            _ => {
+                self.mensaje = "Selecciona una competición concreta para guardarla".to_string();
+            }
+        }
+    }
+
+    pub fn abrir_jornadas(&mut self) {
+        let Some(competicion) = self
+            .partidos
+            .get(self.partido_seleccionado)
+            .map(|p| p.competicion.clone())
+        else {
+            return;
+        };
+        self.jornadas = jornadas::jornadas(&self.todos_partidos, &competicion);
+        if self.jornadas.is_empty() {
+            self.mensaje = format!("{}: no hay partidos con fecha", competicion);
+            return;
+        }
+        self.jornada_actual =
+            jornadas::jornada_actual(&self.jornadas, chrono::Local::now().date_naive());
+        self.vista_actual = Vista::Jornadas;
+    }
+
+    pub fn historia(&self, p: &Partido) -> Option<&HistoriaPartido> {
+        self.historico
+            .get(&historico::clave(&self.todos_partidos, p))
+    }
+
+    pub fn acta(&self, p: &Partido) -> Option<&Acta> {
+        self.actas.get(p.clave_acta()?)
+    }
+
+    /// Descarga el acta en segundo plano; `recibir_acta` la recoge.
+    pub fn descargar_acta(&mut self) {
+        if self.acta_pendiente.is_some() {
+            return;
+        }
+        let Some(p) = self.partidos.get(self.partido_seleccionado).cloned() else {
+            return;
+        };
+        if p.enlace.is_empty() {
+            self.mensaje = "Este partido no tiene enlace al acta".to_string();
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send((p.enlace.clone(), actas::descargar_acta(&p)));
+        });
+        self.acta_pendiente = Some(rx);
+        self.mensaje = "⏳ Descargando acta...".to_string();
+    }
+
+    pub fn recibir_acta(&mut self) {
+        let Some(rx) = &self.acta_pendiente else {
+            return;
+        };
+        let Ok((clave, resultado)) = rx.try_recv() else {
+            return;
+        };
+        self.acta_pendiente = None;
+        match resultado {
+            Ok(acta) => {
+                self.actas.insert(clave, acta);
+                self.mensaje = "✅ Acta descargada".to_string();
+                self.informar(guardar_actas(&self.actas));
+            }
+            Err(e) => self.mensaje = format!("❌ {}", e),
+        }
+    }
+
+    pub fn competicion_jugadores(&self) -> Option<String> {
+        jugadores::competiciones(&self.todos_partidos, &self.actas)
+            .into_iter()
+            .nth(self.jugadores_competicion)
+    }
+
+    pub fn abrir_jugadores(&mut self) {
+        let competiciones = jugadores::competiciones(&self.todos_partidos, &self.actas);
+        if competiciones.is_empty() {
+            self.mensaje = "No hay actas descargadas (V en los detalles de un partido)".to_string();
+            return;
+        }
+        self.jugadores_competicion = self
+            .partidos
+            .get(self.partido_seleccionado)
+            .and_then(|p| competiciones.iter().position(|c| *c == p.competicion))
+            .unwrap_or(0);
+        self.jugadores_equipo = false;
+        self.calcular_jugadores();
+        self.vista_actual = Vista::Jugadores;
+    }
+
+    pub fn cambiar_competicion_jugadores(&mut self, siguiente: bool) {
+        let total = jugadores::competiciones(&self.todos_partidos, &self.actas).len();
+        if siguiente && self.jugadores_competicion + 1 < total {
+            self.jugadores_competicion += 1;
+        } else if !siguiente && self.jugadores_competicion > 0 {
+            self.jugadores_competicion -= 1;
+        } else {
+            return;
+        }
+        self.jugadores_equipo = false;
+        self.calcular_jugadores();
+    }
+
+    /// Alterna entre la competición y el equipo del filtro activo (en todas
+    /// sus competiciones).
+    pub fn alternar_ambito_jugadores(&mut self) {
+        if !self.jugadores_equipo && self.filtro_equipo().is_none() {
+            self.mensaje = "Selecciona primero un filtro de equipo (F)".to_string();
+            return;
+        }
+        self.jugadores_equipo = !self.jugadores_equipo;
+        self.calcular_jugadores();
+    }
+
+    pub fn cambiar_orden_jugadores(&mut self) {
+        self.jugadores_orden = self.jugadores_orden.siguiente();
+        jugadores::ordenar(&mut self.jugadores, self.jugadores_orden);
+        self.jugadores_seleccionado = 0;
+    }
+
+    /// Título del ámbito mostrado: competición o equipo.
+    pub fn ambito_jugadores(&self) -> String {
+        if self.jugadores_equipo {
+            self.filtro_equipo()
+                .map(|f| f.buscar.to_uppercase())
+                .unwrap_or_default()
+        } else {
+            self.competicion_jugadores().unwrap_or_default()
+        }
+    }
+
+    fn calcular_jugadores(&mut self) {
+        self.jugadores = if self.jugadores_equipo {
+            let equipo = self.filtro_equipo().map(|f| f.buscar.clone());
+            jugadores::calcular(&self.todos_partidos, &self.actas, None, equipo.as_deref())
+        } else {
+            let competicion = self.competicion_jugadores();
+            jugadores::calcular(
+                &self.todos_partidos,
+                &self.actas,
+                competicion.as_deref(),
+                None,
+            )
+        };
+        jugadores::ordenar(&mut self.jugadores, self.jugadores_orden);
+        self.jugadores_seleccionado = 0;
+    }
+
+    pub fn exportar_jugadores(&mut self) {
+        self.mensaje = match exportar_texto("jugadores.csv", &jugadores::a_csv(&self.jugadores)) {
+            Ok(ruta) => format!("✅ Jugadores exportados a {}", ruta.display()),
+            Err(e) => format!("❌ {}", e),
+        };
+    }
+}
+
+/// Valor cargado o, si el archivo tiene errores, el valor por defecto
+/// dejando el error anotado.
+fn o_defecto<T: Default>(resultado: Result<T, ErrorDatos>, errores: &mut Vec<ErrorDatos>) -> T {
+    resultado.unwrap_or_else(|e| {
+        errores.push(e);
+        T::default()
+    })
+}
+
+#[cfg(test)]
+pub mod pruebas {
+    use super::App;
+    use fecapa::datos;
+    use std::ops::{Deref, DerefMut};
+    use std::path::PathBuf;
+    use std::sync::{Mutex, PoisonError};
+
+    /// Apps de prueba vivas. Los directorios solo se pueden configurar una
+    /// vez por proceso, así que todas comparten uno y lo borra la última.
+    static VIVAS: Mutex<usize> = Mutex::new(0);
+
+    fn directorio() -> PathBuf {
+        std::env::temp_dir().join(format!("fecapa-pruebas-{}", std::process::id()))
+    }
+
+    /// Una App con los archivos en un directorio temporal, para no tocar
+    /// los datos de quien pasa las pruebas.
+    pub struct AppDePrueba(App);
+
+    impl AppDePrueba {
+        pub fn nueva() -> Self {
+            *VIVAS.lock().unwrap_or_else(PoisonError::into_inner) += 1;
+            datos::configurar_directorios(Some(directorio()), None);
+            assert_eq!(datos::get_data_dir(), directorio());
+            AppDePrueba(App::new())
+        }
+    }
+
+    impl Deref for AppDePrueba {
+        type Target = App;
+
+        fn deref(&self) -> &App {
+            &self.0
+        }
+    }
+
+    impl DerefMut for AppDePrueba {
+        fn deref_mut(&mut self) -> &mut App {
+            &mut self.0
+        }
+    }
+
+    impl Drop for AppDePrueba {
+        fn drop(&mut self) {
+            let mut vivas = VIVAS.lock().unwrap_or_else(PoisonError::into_inner);
+            *vivas -= 1;
+            if *vivas == 0 {
+                let _ = std::fs::remove_dir_all(directorio());
+            }
+        }
     }
 }
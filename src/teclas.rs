@@ -0,0 +1,971 @@
+use crate::acciones::Accion;
+use crate::state::App;
+use crate::vistas::Vista;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Dónde se pulsa una tecla. Son las vistas más los modos que cambian el
+/// significado de las teclas dentro de una (el árbol de competiciones en
+/// los filtros y el nombre del jugador nuevo en la disponibilidad).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Contexto {
+    Partidos,
+    Filtros,
+    Arbol,
+    Detalles,
+    Buscar,
+    Confirmacion,
+    Formulario,
+    Nota,
+    Asistencia,
+    NuevoJugador,
+    CaraACara,
+    Ratings,
+    Estadisticas,
+    Clasificacion,
+    Jornadas,
+    Jugadores,
+    Historial,
+    Errores,
+    Ayuda,
+    Comandos,
+}
+
+/// Nombre de cada contexto en `teclas.json` y título de su ayuda.
+const CONTEXTOS: [(Contexto, &str, &str); 20] = [
+    (Contexto::Partidos, "partidos", "PARTIDOS"),
+    (Contexto::Filtros, "filtros", "FILTROS"),
+    (Contexto::Arbol, "arbol", "COMPETICIONES"),
+    (Contexto::Detalles, "detalles", "DETALLES"),
+    (Contexto::Buscar, "buscar", "BUSCAR"),
+    (Contexto::Confirmacion, "confirmacion", "CONFIRMACIÓN"),
+    (Contexto::Formulario, "formulario", "PARTIDO MANUAL"),
+    (Contexto::Nota, "nota", "NOTA"),
+    (Contexto::Asistencia, "asistencia", "DISPONIBILIDAD"),
+    (Contexto::NuevoJugador, "nuevo_jugador", "NUEVO JUGADOR"),
+    (Contexto::CaraACara, "cara_a_cara", "CARA A CARA"),
+    (Contexto::Ratings, "ratings", "RATINGS"),
+    (Contexto::Estadisticas, "estadisticas", "ESTADÍSTICAS"),
+    (Contexto::Clasificacion, "clasificacion", "CLASIFICACIÓN"),
+    (Contexto::Jornadas, "jornadas", "JORNADAS"),
+    (Contexto::Jugadores, "jugadores", "JUGADORES"),
+    (Contexto::Historial, "historial", "HISTORIAL"),
+    (Contexto::Errores, "errores", "ERRORES"),
+    (Contexto::Ayuda, "ayuda", "AYUDA"),
+    (Contexto::Comandos, "comandos", "COMANDOS"),
+];
+
+impl Contexto {
+    pub fn de(app: &App) -> Self {
+        Self::de_vista(app.vista_actual, app)
+    }
+
+    /// El contexto de `vista` con el resto del estado de `app`; la ayuda lo
+    /// usa con la vista desde la que se abrió.
+    pub fn de_vista(vista: Vista, app: &App) -> Self {
+        match vista {
+            Vista::Partidos => Contexto::Partidos,
+            Vista::Filtros if app.arbol_activo => Contexto::Arbol,
+            Vista::Filtros => Contexto::Filtros,
+            Vista::Detalles => Contexto::Detalles,
+            Vista::Buscar => Contexto::Buscar,
+            Vista::Confirm => Contexto::Confirmacion,
+            Vista::Formulario => Contexto::Formulario,
+            Vista::Nota => Contexto::Nota,
+            Vista::Asistencia if app.asistencia_entrada.is_some() => Contexto::NuevoJugador,
+            Vista::Asistencia => Contexto::Asistencia,
+            Vista::CaraACara => Contexto::CaraACara,
+            Vista::Ratings => Contexto::Ratings,
+            Vista::Estadisticas => Contexto::Estadisticas,
+            Vista::Clasificacion => Contexto::Clasificacion,
+            Vista::Jornadas => Contexto::Jornadas,
+            Vista::Jugadores => Contexto::Jugadores,
+            Vista::Historial => Contexto::Historial,
+            Vista::Errores => Contexto::Errores,
+            Vista::Help => Contexto::Ayuda,
+            Vista::Comandos => Contexto::Comandos,
+        }
+    }
+
+    pub fn nombre(self) -> &'static str {
+        CONTEXTOS.iter().find(|c| c.0 == self).map_or("", |c| c.1)
+    }
+
+    pub fn titulo(self) -> &'static str {
+        CONTEXTOS.iter().find(|c| c.0 == self).map_or("", |c| c.2)
+    }
+
+    /// En los contextos de texto, lo que se escribe con las letras que no
+    /// tienen atajo.
+    pub fn escritura(self) -> Option<&'static str> {
+        match self {
+            Contexto::Buscar => Some("Escribir texto de búsqueda"),
+            Contexto::Formulario => Some("Escribir en el campo"),
+            Contexto::Nota => Some("Escribir la nota"),
+            Contexto::NuevoJugador => Some("Escribir el nombre del jugador"),
+            Contexto::Comandos => Some("Escribir el comando"),
+            _ => None,
+        }
+    }
+}
+
+/// Nombre de cada acción en `teclas.json`. `Escribir` y `Cifra` no están
+/// porque no tienen tecla propia.
+const ACCIONES: [(Accion, &str); 48] = [
+    (Accion::Salir, "salir"),
+    (Accion::Ayuda, "ayuda"),
+    (Accion::Volver, "volver"),
+    (Accion::Aceptar, "aceptar"),
+    (Accion::Subir, "subir"),
+    (Accion::Bajar, "bajar"),
+    (Accion::PaginaArriba, "pagina_arriba"),
+    (Accion::PaginaAbajo, "pagina_abajo"),
+    (Accion::MediaPaginaArriba, "media_pagina_arriba"),
+    (Accion::MediaPaginaAbajo, "media_pagina_abajo"),
+    (Accion::Inicio, "inicio"),
+    (Accion::Fin, "fin"),
+    (Accion::Izquierda, "izquierda"),
+    (Accion::Derecha, "derecha"),
+    (Accion::Siguiente, "siguiente"),
+    (Accion::Anterior, "anterior"),
+    (Accion::Borrar, "borrar"),
+    (Accion::Refrescar, "refrescar"),
+    (Accion::Filtros, "filtros"),
+    (Accion::Buscar, "buscar"),
+    (Accion::Detalles, "detalles"),
+    (Accion::NuevoPartido, "nuevo_partido"),
+    (Accion::Favorito, "favorito"),
+    (Accion::Asistencia, "asistencia"),
+    (Accion::OrdenDistancia, "orden_distancia"),
+    (Accion::Ratings, "ratings"),
+    (Accion::Estadisticas, "estadisticas"),
+    (Accion::Clasificacion, "clasificacion"),
+    (Accion::Jornadas, "jornadas"),
+    (Accion::Jugadores, "jugadores"),
+    (Accion::Temporada, "temporada"),
+    (Accion::Errores, "errores"),
+    (Accion::Historial, "historial"),
+    (Accion::GuardarFiltro, "guardar_filtro"),
+    (Accion::Eliminar, "eliminar"),
+    (Accion::Nota, "nota"),
+    (Accion::CaraACara, "cara_a_cara"),
+    (Accion::DescargarActa, "descargar_acta"),
+    (Accion::EditarPartido, "editar_partido"),
+    (Accion::FiltrarLocal, "filtrar_local"),
+    (Accion::FiltrarCompeticion, "filtrar_competicion"),
+    (Accion::Disponibilidad, "disponibilidad"),
+    (Accion::NuevoJugador, "nuevo_jugador"),
+    (Accion::Exportar, "exportar"),
+    (Accion::Ordenar, "ordenar"),
+    (Accion::ApartarArchivo, "apartar_archivo"),
+    (Accion::Comandos, "comandos"),
+    (Accion::Completar, "completar"),
+];
+
+fn nombre_accion(accion: Accion) -> &'static str {
+    ACCIONES
+        .iter()
+        .find(|a| a.0 == accion)
+        .map_or("escribir", |a| a.1)
+}
+
+/// Una tecla, con Ctrl o sin él. Se escribe como en la ayuda: "q", "?",
+/// "Enter", "Esc", "Tab", "S-Tab", "Backspace", "Up", "Down", "Left",
+/// "Right", "PgUp", "PgDn", "Space", "F5" o "Ctrl-d". También se aceptan
+/// los nombres con los que se muestran ("↑", "AvPag", "Espacio"...).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Tecla {
+    pub codigo: KeyCode,
+    pub ctrl: bool,
+}
+
+impl Tecla {
+    pub fn coincide(&self, evento: &KeyEvent) -> bool {
+        self.codigo == evento.code && self.ctrl == evento.modifiers.contains(KeyModifiers::CONTROL)
+    }
+}
+
+impl FromStr for Tecla {
+    type Err = String;
+
+    fn from_str(texto: &str) -> Result<Self, Self::Err> {
+        let (ctrl, nombre) = match texto.strip_prefix("Ctrl-") {
+            Some(resto) => (true, resto),
+            None => (false, texto),
+        };
+        let desconocida = || format!("tecla desconocida «{}»", texto);
+        let codigo = match nombre {
+            "Enter" => KeyCode::Enter,
+            "Esc" => KeyCode::Esc,
+            "Tab" => KeyCode::Tab,
+            "S-Tab" => KeyCode::BackTab,
+            "Backspace" => KeyCode::Backspace,
+            "Up" | "↑" => KeyCode::Up,
+            "Down" | "↓" => KeyCode::Down,
+            "Left" | "←" => KeyCode::Left,
+            "Right" | "→" => KeyCode::Right,
+            "PgUp" | "RePag" => KeyCode::PageUp,
+            "PgDn" | "AvPag" => KeyCode::PageDown,
+            "Space" | "Espacio" => KeyCode::Char(' '),
+            _ => {
+                let mut caracteres = nombre.chars();
+                match (caracteres.next(), caracteres.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    (Some('F'), Some(_)) => {
+                        KeyCode::F(nombre[1..].parse().map_err(|_| desconocida())?)
+                    }
+                    _ => return Err(desconocida()),
+                }
+            }
+        };
+        Ok(Tecla { codigo, ctrl })
+    }
+}
+
+impl fmt::Display for Tecla {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl-")?;
+        }
+        match self.codigo {
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "S-Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::PageUp => write!(f, "RePag"),
+            KeyCode::PageDown => write!(f, "AvPag"),
+            KeyCode::Char(' ') => write!(f, "Espacio"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            otra => write!(f, "{:?}", otra),
+        }
+    }
+}
+
+/// Cómo se muestran unas teclas: si están la minúscula y la mayúscula de
+/// una letra, solo la mayúscula.
+fn nombres(teclas: &[Tecla]) -> Vec<String> {
+    teclas
+        .iter()
+        .filter(|t| match t.codigo {
+            KeyCode::Char(c) if c.is_lowercase() => !teclas.iter().any(|otra| {
+                otra.ctrl == t.ctrl && otra.codigo == KeyCode::Char(c.to_ascii_uppercase())
+            }),
+            _ => true,
+        })
+        .map(Tecla::to_string)
+        .collect()
+}
+
+/// Una o varias teclas que hacen lo mismo en un contexto.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Atajo {
+    pub contexto: Contexto,
+    pub teclas: Vec<Tecla>,
+    pub accion: Accion,
+    /// Lo que hace, para la ayuda.
+    pub descripcion: &'static str,
+}
+
+use Accion as A;
+use Contexto as C;
+
+/// Teclas por contexto, en el orden de la ayuda. Las acciones seguidas con
+/// la misma descripción salen en una sola línea. Las letras van en
+/// mayúscula y minúscula salvo `q`, para no salir por accidente con el
+/// bloqueo de mayúsculas.
+const POR_DEFECTO: &[(Contexto, &str, Accion, &str)] = &[
+    (C::Partidos, "Up k", A::Subir, "Navegar por partidos"),
+    (C::Partidos, "Down j", A::Bajar, "Navegar por partidos"),
+    (
+        C::Partidos,
+        "PgDn",
+        A::PaginaAbajo,
+        "Página siguiente/anterior",
+    ),
+    (
+        C::Partidos,
+        "PgUp",
+        A::PaginaArriba,
+        "Página siguiente/anterior",
+    ),
+    (
+        C::Partidos,
+        "Ctrl-d",
+        A::MediaPaginaAbajo,
+        "Media página siguiente/anterior",
+    ),
+    (
+        C::Partidos,
+        "Ctrl-u",
+        A::MediaPaginaArriba,
+        "Media página siguiente/anterior",
+    ),
+    (C::Partidos, "g", A::Inicio, "Primer partido"),
+    (
+        C::Partidos,
+        "G",
+        A::Fin,
+        "Último partido (con número delante, ese partido)",
+    ),
+    (
+        C::Partidos,
+        "Enter e",
+        A::Detalles,
+        "Ver detalles del partido",
+    ),
+    (C::Partidos, "f F", A::Filtros, "Ver lista de filtros"),
+    (C::Partidos, "/", A::Buscar, "Buscar texto"),
+    (
+        C::Partidos,
+        ":",
+        A::Comandos,
+        "Línea de comandos (:filter, :team, :goto, :sort, :export)",
+    ),
+    (
+        C::Partidos,
+        "n N",
+        A::NuevoPartido,
+        "Nuevo partido manual (amistoso, torneo...)",
+    ),
+    (C::Partidos, "*", A::Favorito, "Marcar/desmarcar favorito"),
+    (
+        C::Partidos,
+        "d D",
+        A::Asistencia,
+        "Disponibilidad de jugadores (filtro de equipo)",
+    ),
+    (
+        C::Partidos,
+        "o O",
+        A::OrdenDistancia,
+        "Partidos fuera por distancia / orden original",
+    ),
+    (
+        C::Partidos,
+        "i I",
+        A::Ratings,
+        "Ratings Elo por competición",
+    ),
+    (
+        C::Partidos,
+        "s S",
+        A::Estadisticas,
+        "Estadísticas y gráficos del equipo filtrado",
+    ),
+    (
+        C::Partidos,
+        "c C",
+        A::Clasificacion,
+        "Clasificación y proyección de la competición",
+    ),
+    (
+        C::Partidos,
+        "w W",
+        A::Jornadas,
+        "Jornadas de la competición",
+    ),
+    (
+        C::Partidos,
+        "p P",
+        A::Jugadores,
+        "Goleadores y tarjetas (actas descargadas)",
+    ),
+    (
+        C::Partidos,
+        "t T",
+        A::Temporada,
+        "Cambiar de temporada (archivo)",
+    ),
+    (
+        C::Partidos,
+        "!",
+        A::Errores,
+        "Errores en los archivos de datos",
+    ),
+    (
+        C::Partidos,
+        "h H",
+        A::Historial,
+        "Historial del equipo filtrado por temporadas",
+    ),
+    (
+        C::Partidos,
+        "r R",
+        A::Refrescar,
+        "Refrescar datos (fuentes de ajustes.json)",
+    ),
+    (C::Partidos, "?", A::Ayuda, "Ver esta ayuda"),
+    (C::Partidos, "q", A::Salir, "Salir de la aplicación"),
+    (C::Filtros, "Up k", A::Subir, "Navegar por filtros"),
+    (C::Filtros, "Down j", A::Bajar, "Navegar por filtros"),
+    (
+        C::Filtros,
+        "Tab",
+        A::Siguiente,
+        "Árbol de competiciones (categoría → nivel → grupo)",
+    ),
+    (
+        C::Filtros,
+        "Enter",
+        A::Aceptar,
+        "Aplicar filtro seleccionado",
+    ),
+    (
+        C::Filtros,
+        "d D",
+        A::Eliminar,
+        "Eliminar filtro (excepto 'Todos')",
+    ),
+    (C::Filtros, "Esc", A::Volver, "Volver a partidos"),
+    (C::Filtros, "?", A::Ayuda, "Ver esta ayuda"),
+    (C::Arbol, "Up k", A::Subir, "Navegar por competiciones"),
+    (C::Arbol, "Down j", A::Bajar, "Navegar por competiciones"),
+    (
+        C::Arbol,
+        "Enter Right",
+        A::Aceptar,
+        "Desplegar / ver sus partidos",
+    ),
+    (C::Arbol, "Left", A::Izquierda, "Plegar"),
+    (
+        C::Arbol,
+        "a A",
+        A::GuardarFiltro,
+        "Guardar competición como filtro",
+    ),
+    (
+        C::Arbol,
+        "Tab",
+        A::Siguiente,
+        "Volver a la lista de filtros",
+    ),
+    (C::Arbol, "Esc", A::Volver, "Volver a partidos"),
+    (C::Arbol, "?", A::Ayuda, "Ver esta ayuda"),
+    (C::Detalles, "Up k", A::Subir, "Navegar por campos"),
+    (C::Detalles, "Down j", A::Bajar, "Navegar por campos"),
+    (
+        C::Detalles,
+        "a A",
+        A::FiltrarLocal,
+        "Añadir equipo local como filtro",
+    ),
+    (
+        C::Detalles,
+        "c C",
+        A::FiltrarCompeticion,
+        "Añadir competición como filtro",
+    ),
+    (C::Detalles, "*", A::Favorito, "Marcar/desmarcar favorito"),
+    (C::Detalles, "n N", A::Nota, "Escribir nota privada"),
+    (
+        C::Detalles,
+        "h H",
+        A::CaraACara,
+        "Historial cara a cara entre los dos equipos",
+    ),
+    (
+        C::Detalles,
+        "v V",
+        A::DescargarActa,
+        "Descargar el acta (goles, tarjetas, árbitros)",
+    ),
+    (
+        C::Detalles,
+        "m M",
+        A::EditarPartido,
+        "Editar partido manual",
+    ),
+    (C::Detalles, "x X", A::Eliminar, "Eliminar partido manual"),
+    (C::Detalles, "Esc", A::Volver, "Volver a partidos"),
+    (C::Detalles, "?", A::Ayuda, "Ver esta ayuda"),
+    (C::Buscar, "Backspace", A::Borrar, "Borrar último carácter"),
+    (C::Buscar, "Enter", A::Aceptar, "Confirmar búsqueda"),
+    (C::Buscar, "Esc", A::Volver, "Cancelar búsqueda"),
+    (C::Buscar, "?", A::Ayuda, "Ver esta ayuda"),
+    (
+        C::Confirmacion,
+        "Up Down k j",
+        A::Bajar,
+        "Seleccionar opción",
+    ),
+    (C::Confirmacion, "Enter", A::Aceptar, "Confirmar acción"),
+    (C::Confirmacion, "Esc", A::Volver, "Cancelar y volver"),
+    (C::Confirmacion, "?", A::Ayuda, "Ver esta ayuda"),
+    (C::Formulario, "Up", A::Subir, "Cambiar de campo"),
+    (C::Formulario, "Down Tab", A::Bajar, "Cambiar de campo"),
+    (
+        C::Formulario,
+        "Backspace",
+        A::Borrar,
+        "Borrar último carácter",
+    ),
+    (C::Formulario, "Enter", A::Aceptar, "Guardar partido"),
+    (C::Formulario, "Esc", A::Volver, "Cancelar"),
+    (C::Nota, "Backspace", A::Borrar, "Borrar último carácter"),
+    (
+        C::Nota,
+        "Enter",
+        A::Aceptar,
+        "Guardar (vacía para eliminarla)",
+    ),
+    (C::Nota, "Esc", A::Volver, "Cancelar"),
+    (C::Asistencia, "Up k", A::Subir, "Seleccionar jugador"),
+    (C::Asistencia, "Down j", A::Bajar, "Seleccionar jugador"),
+    (C::Asistencia, "Tab PgDn", A::Siguiente, "Partido siguiente"),
+    (C::Asistencia, "S-Tab PgUp", A::Anterior, "Partido anterior"),
+    (
+        C::Asistencia,
+        "Space Left Right",
+        A::Disponibilidad,
+        "Cambiar sí / no / quizá / sin respuesta",
+    ),
+    (
+        C::Asistencia,
+        "a A",
+        A::NuevoJugador,
+        "Añadir jugador a la plantilla",
+    ),
+    (
+        C::Asistencia,
+        "x X",
+        A::Eliminar,
+        "Eliminar jugador de la plantilla",
+    ),
+    (
+        C::Asistencia,
+        "e E",
+        A::Exportar,
+        "Exportar convocatoria a asistencia.txt",
+    ),
+    (C::Asistencia, "Esc", A::Volver, "Volver a partidos"),
+    (C::Asistencia, "?", A::Ayuda, "Ver esta ayuda"),
+    (
+        C::NuevoJugador,
+        "Backspace",
+        A::Borrar,
+        "Borrar último carácter",
+    ),
+    (
+        C::NuevoJugador,
+        "Enter",
+        A::Aceptar,
+        "Añadir a la plantilla",
+    ),
+    (C::NuevoJugador, "Esc", A::Volver, "Cancelar"),
+    (C::CaraACara, "Esc", A::Volver, "Volver a detalles"),
+    (C::CaraACara, "?", A::Ayuda, "Ver esta ayuda"),
+    (
+        C::Ratings,
+        "Left",
+        A::Izquierda,
+        "Competición anterior/siguiente",
+    ),
+    (
+        C::Ratings,
+        "Right",
+        A::Derecha,
+        "Competición anterior/siguiente",
+    ),
+    (C::Ratings, "Esc", A::Volver, "Volver a partidos"),
+    (C::Ratings, "?", A::Ayuda, "Ver esta ayuda"),
+    (C::Estadisticas, "Esc", A::Volver, "Volver a partidos"),
+    (C::Estadisticas, "?", A::Ayuda, "Ver esta ayuda"),
+    (
+        C::Clasificacion,
+        "Left",
+        A::Izquierda,
+        "Competición anterior/siguiente",
+    ),
+    (
+        C::Clasificacion,
+        "Right",
+        A::Derecha,
+        "Competición anterior/siguiente",
+    ),
+    (
+        C::Clasificacion,
+        "Tab",
+        A::Siguiente,
+        "Clasificación / Proyección",
+    ),
+    (C::Clasificacion, "Esc", A::Volver, "Volver a partidos"),
+    (C::Clasificacion, "?", A::Ayuda, "Ver esta ayuda"),
+    (
+        C::Jornadas,
+        "Left",
+        A::Izquierda,
+        "Jornada anterior/siguiente",
+    ),
+    (
+        C::Jornadas,
+        "Right",
+        A::Derecha,
+        "Jornada anterior/siguiente",
+    ),
+    (C::Jornadas, "Esc", A::Volver, "Volver a partidos"),
+    (C::Jornadas, "?", A::Ayuda, "Ver esta ayuda"),
+    (C::Jugadores, "Up k", A::Subir, "Navegar por jugadores"),
+    (C::Jugadores, "Down j", A::Bajar, "Navegar por jugadores"),
+    (
+        C::Jugadores,
+        "Left",
+        A::Izquierda,
+        "Competición anterior/siguiente",
+    ),
+    (
+        C::Jugadores,
+        "Right",
+        A::Derecha,
+        "Competición anterior/siguiente",
+    ),
+    (
+        C::Jugadores,
+        "Tab",
+        A::Siguiente,
+        "Competición / equipo del filtro activo",
+    ),
+    (
+        C::Jugadores,
+        "o O",
+        A::Ordenar,
+        "Ordenar por goles, goles/partido, tarjetas o nombre",
+    ),
+    (
+        C::Jugadores,
+        "e E",
+        A::Exportar,
+        "Exportar la tabla a jugadores.csv",
+    ),
+    (C::Jugadores, "Esc", A::Volver, "Volver a partidos"),
+    (C::Jugadores, "?", A::Ayuda, "Ver esta ayuda"),
+    (C::Historial, "Esc", A::Volver, "Volver a partidos"),
+    (C::Historial, "?", A::Ayuda, "Ver esta ayuda"),
+    (C::Errores, "Up k", A::Subir, "Seleccionar error"),
+    (C::Errores, "Down j", A::Bajar, "Seleccionar error"),
+    (
+        C::Errores,
+        "d D",
+        A::ApartarArchivo,
+        "Apartar el archivo (queda una copia) y volver a guardar",
+    ),
+    (C::Errores, "Esc", A::Volver, "Volver a partidos"),
+    (C::Errores, "?", A::Ayuda, "Ver esta ayuda"),
+    (C::Ayuda, "Esc", A::Volver, "Cerrar ayuda"),
+    (
+        C::Comandos,
+        "Tab",
+        A::Completar,
+        "Completar comando, equipo o competición",
+    ),
+    (
+        C::Comandos,
+        "Backspace",
+        A::Borrar,
+        "Borrar último carácter",
+    ),
+    (C::Comandos, "Enter", A::Aceptar, "Ejecutar"),
+    (C::Comandos, "Esc", A::Volver, "Cancelar"),
+];
+
+/// Las teclas de `teclas.json`, sin interpretar: contexto → acción → teclas.
+pub type AjustesTeclas = BTreeMap<String, BTreeMap<String, String>>;
+
+/// La tabla de atajos con la que se traducen las teclas y se escriben la
+/// ayuda y las pistas de los títulos.
+#[derive(Debug, Clone)]
+pub struct Teclado {
+    pub atajos: Vec<Atajo>,
+}
+
+fn leer_teclas(texto: &str) -> Result<Vec<Tecla>, String> {
+    texto.split_whitespace().map(str::parse).collect()
+}
+
+/// Teclas repetidas entre atajos de un mismo contexto.
+fn conflictos(atajos: &[Atajo]) -> Vec<String> {
+    let mut conflictos = Vec::new();
+    for (i, a) in atajos.iter().enumerate() {
+        for b in &atajos[i + 1..] {
+            for tecla in a.teclas.iter().filter(|t| b.teclas.contains(t)) {
+                conflictos.push(format!(
+                    "«{}» está en {}.{} y en {}.{}",
+                    tecla,
+                    a.contexto.nombre(),
+                    nombre_accion(a.accion),
+                    b.contexto.nombre(),
+                    nombre_accion(b.accion)
+                ));
+            }
+        }
+    }
+    conflictos
+}
+
+impl Teclado {
+    pub fn por_defecto() -> Self {
+        let atajos = POR_DEFECTO
+            .iter()
+            .map(|&(contexto, teclas, accion, descripcion)| Atajo {
+                contexto,
+                teclas: leer_teclas(teclas).expect("teclas por defecto válidas"),
+                accion,
+                descripcion,
+            })
+            .collect();
+        Self { atajos }
+    }
+
+    /// Las teclas por defecto cambiadas por las de `teclas.json`. Cada
+    /// entrada sustituye todas las teclas de una acción en un contexto (una
+    /// cadena vacía la deja sin tecla). Devuelve también los problemas: las
+    /// entradas que no se entienden se ignoran y, si quedan teclas
+    /// repetidas en un contexto, ese contexto se queda con las de por
+    /// defecto.
+    pub fn con_ajustes(ajustes: &AjustesTeclas) -> (Self, Vec<String>) {
+        let mut teclado = Self::por_defecto();
+        let mut problemas = Vec::new();
+        for (nombre, acciones) in ajustes {
+            let Some(&(contexto, ..)) = CONTEXTOS.iter().find(|c| c.1 == nombre) else {
+                problemas.push(format!("contexto desconocido «{}»", nombre));
+                continue;
+            };
+            let mut atajos: Vec<Atajo> = teclado
+                .atajos
+                .iter()
+                .filter(|a| a.contexto == contexto)
+                .cloned()
+                .collect();
+            for (accion, teclas) in acciones {
+                let Some(atajo) = atajos
+                    .iter_mut()
+                    .find(|a| nombre_accion(a.accion) == accion)
+                else {
+                    problemas.push(format!(
+                        "{}.{}: no es una acción de {}",
+                        nombre, accion, nombre
+                    ));
+                    continue;
+                };
+                match leer_teclas(teclas) {
+                    Ok(teclas) => atajo.teclas = teclas,
+                    Err(e) => problemas.push(format!("{}.{}: {}", nombre, accion, e)),
+                }
+            }
+            let repetidas = conflictos(&atajos);
+            if !repetidas.is_empty() {
+                for conflicto in repetidas {
+                    problemas.push(format!(
+                        "{}; se usan las teclas por defecto en {}",
+                        conflicto, nombre
+                    ));
+                }
+                continue;
+            }
+            let mut nuevos = atajos.into_iter();
+            for atajo in teclado.atajos.iter_mut().filter(|a| a.contexto == contexto) {
+                *atajo = nuevos.next().expect("mismos atajos");
+            }
+        }
+        (teclado, problemas)
+    }
+
+    /// La acción de `evento` en `contexto`. En los contextos de texto, lo
+    /// que no es un atajo se escribe; en la lista de partidos, las cifras
+    /// que no son un atajo cuentan las repeticiones de la siguiente acción.
+    pub fn accion(&self, contexto: Contexto, evento: &KeyEvent) -> Option<Accion> {
+        let atajo = self
+            .atajos
+            .iter()
+            .filter(|a| a.contexto == contexto)
+            .find(|a| a.teclas.iter().any(|t| t.coincide(evento)));
+        if let Some(atajo) = atajo {
+            return Some(atajo.accion);
+        }
+        match evento.code {
+            KeyCode::Char(c)
+                if contexto.escritura().is_some()
+                    && !evento.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                Some(Accion::Escribir(c))
+            }
+            KeyCode::Char(c)
+                if contexto == Contexto::Partidos
+                    && !evento.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                c.to_digit(10).map(|cifra| Accion::Cifra(cifra as u8))
+            }
+            _ => None,
+        }
+    }
+
+    /// La primera tecla de `accion` en `contexto`, como se muestra.
+    pub fn tecla(&self, contexto: Contexto, accion: Accion) -> Option<String> {
+        self.atajos
+            .iter()
+            .find(|a| a.contexto == contexto && a.accion == accion)
+            .and_then(|a| nombres(&a.teclas).into_iter().next())
+    }
+
+    /// Pistas para los títulos y la barra de estado, como
+    /// "↑↓ Navegar | A Añadir filtro | Esc Volver". Cada pista junta las
+    /// teclas de varias acciones; las que se han quedado sin tecla no salen.
+    pub fn pistas(&self, contexto: Contexto, pistas: &[(&[Accion], &str)]) -> String {
+        pistas
+            .iter()
+            .filter_map(|(acciones, texto)| {
+                let teclas: Vec<String> = acciones
+                    .iter()
+                    .filter_map(|&a| self.tecla(contexto, a))
+                    .collect();
+                if teclas.is_empty() {
+                    return None;
+                }
+                let separador = if teclas.iter().all(|t| t.chars().count() == 1) {
+                    ""
+                } else {
+                    "/"
+                };
+                Some(format!("{} {}", teclas.join(separador), texto))
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    /// Líneas (teclas, descripción) de la ayuda de `contexto`.
+    pub fn ayuda(&self, contexto: Contexto) -> Vec<(String, &'static str)> {
+        let mut lineas: Vec<(Vec<String>, &'static str)> = Vec::new();
+        if let Some(escritura) = contexto.escritura() {
+            lineas.push((vec!["A-Z, 0-9".to_string()], escritura));
+        }
+        for atajo in self.atajos.iter().filter(|a| a.contexto == contexto) {
+            let teclas = nombres(&atajo.teclas);
+            if teclas.is_empty() {
+                continue;
+            }
+            match lineas.last_mut() {
+                Some((anteriores, descripcion)) if *descripcion == atajo.descripcion => {
+                    anteriores.extend(teclas)
+                }
+                _ => lineas.push((teclas, atajo.descripcion)),
+            }
+        }
+        lineas
+            .into_iter()
+            .map(|(teclas, descripcion)| (teclas.join(" / "), descripcion))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tecla(codigo: KeyCode) -> KeyEvent {
+        KeyEvent::new(codigo, KeyModifiers::NONE)
+    }
+
+    fn ajustes(contexto: &str, accion: &str, teclas: &str) -> AjustesTeclas {
+        BTreeMap::from([(
+            contexto.to_string(),
+            BTreeMap::from([(accion.to_string(), teclas.to_string())]),
+        )])
+    }
+
+    #[test]
+    fn la_misma_tecla_cambia_segun_el_contexto() {
+        let teclado = Teclado::por_defecto();
+        let d = tecla(KeyCode::Char('d'));
+        assert_eq!(
+            teclado.accion(Contexto::Partidos, &d),
+            Some(Accion::Asistencia)
+        );
+        assert_eq!(
+            teclado.accion(Contexto::Filtros, &d),
+            Some(Accion::Eliminar)
+        );
+        assert_eq!(
+            teclado.accion(Contexto::Buscar, &d),
+            Some(Accion::Escribir('d'))
+        );
+        assert_eq!(teclado.accion(Contexto::Ratings, &d), None);
+        // En la nota el ? se escribe; en la búsqueda abre la ayuda.
+        let interrogante = tecla(KeyCode::Char('?'));
+        assert_eq!(
+            teclado.accion(Contexto::Nota, &interrogante),
+            Some(Accion::Escribir('?'))
+        );
+        assert_eq!(
+            teclado.accion(Contexto::Buscar, &interrogante),
+            Some(Accion::Ayuda)
+        );
+    }
+
+    #[test]
+    fn las_teclas_se_leen_como_se_escriben() {
+        for texto in ["q", "?", "Enter", "S-Tab", "PgDn", "Ctrl-d", "F5"] {
+            let tecla: Tecla = texto.parse().unwrap();
+            assert_eq!(tecla.to_string().parse::<Tecla>(), Ok(tecla), "{}", texto);
+        }
+        assert!("Hiper".parse::<Tecla>().is_err());
+        assert!("Fx".parse::<Tecla>().is_err());
+    }
+
+    #[test]
+    fn las_teclas_por_defecto_no_se_repiten() {
+        let teclado = Teclado::por_defecto();
+        for &(contexto, ..) in &CONTEXTOS {
+            let atajos: Vec<Atajo> = teclado
+                .atajos
+                .iter()
+                .filter(|a| a.contexto == contexto)
+                .cloned()
+                .collect();
+            assert_eq!(conflictos(&atajos), Vec::<String>::new());
+        }
+    }
+
+    #[test]
+    fn teclas_json_cambia_la_ayuda_y_las_pistas() {
+        let (teclado, problemas) = Teclado::con_ajustes(&ajustes("partidos", "refrescar", "F5"));
+        assert!(problemas.is_empty(), "{:?}", problemas);
+        let f5 = tecla(KeyCode::F(5));
+        assert_eq!(
+            teclado.accion(Contexto::Partidos, &f5),
+            Some(Accion::Refrescar)
+        );
+        assert_eq!(
+            teclado.accion(Contexto::Partidos, &tecla(KeyCode::Char('r'))),
+            None
+        );
+        assert!(teclado.ayuda(Contexto::Partidos).contains(&(
+            "F5".to_string(),
+            "Refrescar datos (fuentes de ajustes.json)"
+        )));
+        assert_eq!(
+            teclado.pistas(Contexto::Partidos, &[(&[Accion::Refrescar], "Refrescar")]),
+            "F5 Refrescar"
+        );
+    }
+
+    #[test]
+    fn un_conflicto_deja_el_contexto_por_defecto() {
+        let (teclado, problemas) = Teclado::con_ajustes(&ajustes("detalles", "nota", "c"));
+        assert_eq!(problemas.len(), 1);
+        assert!(problemas[0].contains("«c»"), "{}", problemas[0]);
+        assert_eq!(
+            teclado.accion(Contexto::Detalles, &tecla(KeyCode::Char('n'))),
+            Some(Accion::Nota)
+        );
+
+        let (_, problemas) = Teclado::con_ajustes(&ajustes("partidos", "ordenar", "x"));
+        assert_eq!(problemas.len(), 1);
+    }
+}
@@ -1,11 +1,25 @@
-use crate::models::Vista;
+use crate::acciones::Accion;
 use crate::state::App;
+use crate::teclas::Contexto;
+use crate::vistas::{Vista, CAMPOS_FORMULARIO};
+use fecapa::clasificacion;
+use fecapa::datos::{directorios, ErrorDatos};
+use fecapa::enfrentamientos;
+use fecapa::historico;
+use fecapa::jornadas;
+use fecapa::jugadores::AZULES_POR_SANCION;
+use fecapa::models::{Acta, Disponibilidad, Lado, Origen, Partido, TipoEvento};
+use fecapa::pistas;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     prelude::Stylize,
     style::{Color, Style},
+    symbols::Marker,
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, List, ListItem, Paragraph, Row, Table},
+    widgets::{
+        Axis, BarChart, Block, Borders, Cell, Chart, Dataset, GraphType, List, ListItem, Paragraph,
+        Row, Table, Tabs,
+    },
     Frame,
 };
 
@@ -18,13 +32,95 @@ pub fn truncate(s: &str, max_len: usize) -> String {
     }
 }
 
-pub fn render_title(f: &mut Frame, area: Rect) {
-    let title = Paragraph::new("🏒 HOQUEI PATINS - COMPETICIÓN")
-        .style(Style::default().fg(Color::Green).bold())
-        .alignment(ratatui::layout::Alignment::Center);
+/// Título de un bloque con las pistas de teclas de `contexto` detrás. Si
+/// ninguna acción tiene tecla, solo el texto.
+fn titulo(texto: &str, app: &App, contexto: Contexto, pistas: &[(&[Accion], &str)]) -> String {
+    let pistas = app.teclado.pistas(contexto, pistas);
+    if pistas.is_empty() {
+        format!(" {} ", texto)
+    } else {
+        format!(" {} - {} ", texto, pistas)
+    }
+}
+
+pub fn render_title(f: &mut Frame, area: Rect, app: &App) {
+    let archivada = app.temporadas().last().is_some_and(|t| *t != app.temporada);
+    let texto = if app.temporada.is_empty() {
+        "🏒 HOQUEI PATINS - COMPETICIÓN".to_string()
+    } else {
+        let temporada = if archivada {
+            format!("🏒 HOQUEI PATINS - TEMPORADA {} (ARCHIVO)", app.temporada)
+        } else {
+            format!("🏒 HOQUEI PATINS - TEMPORADA {}", app.temporada)
+        };
+        titulo(
+            &temporada,
+            app,
+            Contexto::Partidos,
+            &[(&[Accion::Temporada], "Cambiar")],
+        )
+        .trim()
+        .to_string()
+    };
+    let texto = match &directorios().perfil {
+        Some(perfil) => format!("{} - PERFIL {}", texto, perfil.to_uppercase()),
+        None => texto,
+    };
+    let color = if archivada {
+        Color::Yellow
+    } else {
+        Color::Green
+    };
+    let actualizado = match (app.actualizado, app.antiguedad()) {
+        (Some(fecha), Some(antiguedad)) => {
+            let mut texto = format!(
+                "Actualizado {} ({})",
+                formatear_antiguedad(antiguedad),
+                fecha.format("%d/%m %H:%M")
+            );
+            if !app.fuente_datos.is_empty() {
+                texto.push_str(&format!(" - {}", app.fuente_datos));
+            }
+            texto
+        }
+        _ => titulo(
+            "Sin datos descargados",
+            app,
+            Contexto::Partidos,
+            &[(&[Accion::Refrescar], "Refrescar")],
+        )
+        .trim()
+        .to_string(),
+    };
+    let color_actualizado = if app.datos_caducados() {
+        Color::Yellow
+    } else {
+        Color::DarkGray
+    };
+    let title = Paragraph::new(vec![
+        Line::from(Span::styled(texto, Style::default().fg(color).bold())),
+        Line::from(Span::styled(
+            actualizado,
+            Style::default().fg(color_actualizado),
+        )),
+    ])
+    .alignment(ratatui::layout::Alignment::Center);
     f.render_widget(title, area);
 }
 
+/// "hace 5 min", "hace 3 h", "hace 2 días".
+fn formatear_antiguedad(antiguedad: chrono::Duration) -> String {
+    if antiguedad.num_minutes() < 1 {
+        "hace un momento".to_string()
+    } else if antiguedad.num_hours() < 1 {
+        format!("hace {} min", antiguedad.num_minutes())
+    } else if antiguedad.num_days() < 2 {
+        format!("hace {} h", antiguedad.num_hours())
+    } else {
+        format!("hace {} días", antiguedad.num_days())
+    }
+}
+
 pub fn render_partidos_table(f: &mut Frame, area: Rect, app: &App) {
     let table_height = (area.height as usize).saturating_sub(2);
     let offset = app.partido_seleccionado.saturating_sub(table_height / 2);
@@ -106,28 +202,23 @@ pub fn render_partidos_table(f: &mut Frame, area: Rect, app: &App) {
             let real_index = offset + i;
             let style = if real_index == app.partido_seleccionado {
                 Style::default().bg(Color::Blue).fg(Color::White)
+            } else if p.origen == Origen::Manual {
+                Style::default().fg(Color::Magenta)
             } else if p.resultado.is_empty() {
                 Style::default().fg(Color::Green)
             } else {
                 Style::default()
             };
 
-            let cells = if width < 90 {
-                vec![
-                    Cell::from(truncate(&p.competicion, max_lens.0)),
-                    Cell::from(truncate(&p.data, max_lens.1)),
-                    Cell::from(truncate(&p.hora, max_lens.2)),
-                    Cell::from(truncate(&p.local, max_lens.3)),
-                    Cell::from(truncate(&p.visitante, max_lens.4)),
-                    Cell::from(if p.resultado.is_empty() {
-                        "-".to_string()
-                    } else {
-                        p.resultado.clone()
-                    }),
-                ]
-            } else if width < 120 {
+            let competicion = if app.anotaciones.es_favorito(p) {
+                format!("★ {}", p.competicion)
+            } else {
+                p.competicion.clone()
+            };
+
+            let cells = if width < 120 {
                 vec![
-                    Cell::from(truncate(&p.competicion, max_lens.0)),
+                    Cell::from(truncate(&competicion, max_lens.0)),
                     Cell::from(truncate(&p.data, max_lens.1)),
                     Cell::from(truncate(&p.hora, max_lens.2)),
                     Cell::from(truncate(&p.local, max_lens.3)),
@@ -140,7 +231,7 @@ pub fn render_partidos_table(f: &mut Frame, area: Rect, app: &App) {
                 ]
             } else {
                 vec![
-                    Cell::from(truncate(&p.competicion, max_lens.0)),
+                    Cell::from(truncate(&competicion, max_lens.0)),
                     Cell::from(truncate(&p.data, max_lens.1)),
                     Cell::from(truncate(&p.hora, max_lens.2)),
                     Cell::from(truncate(&p.local, max_lens.3)),
@@ -172,6 +263,11 @@ pub fn render_partidos_table(f: &mut Frame, area: Rect, app: &App) {
 }
 
 pub fn render_filtros_list(f: &mut Frame, area: Rect, app: &App) {
+    if app.arbol_activo {
+        render_arbol_competiciones(f, area, app);
+        return;
+    }
+
     let items: Vec<ListItem> = app
         .filtros
         .iter()
@@ -189,7 +285,12 @@ pub fn render_filtros_list(f: &mut Frame, area: Rect, app: &App) {
     let list = List::new(items)
         .block(
             Block::bordered()
-                .title(" Filtros ")
+                .title(titulo(
+                    "Filtros",
+                    app,
+                    Contexto::Filtros,
+                    &[(&[Accion::Siguiente], "Competiciones")],
+                ))
                 .border_style(Style::default().fg(Color::Cyan))
                 .borders(Borders::ALL),
         )
@@ -207,23 +308,204 @@ pub fn render_filtros_list(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(list, render_area);
 }
 
+fn render_arbol_competiciones(f: &mut Frame, area: Rect, app: &App) {
+    let nodos = app.nodos_arbol();
+    let alto = (area.height as usize).saturating_sub(2);
+    let offset = app.arbol_seleccionado.saturating_sub(alto / 2);
+
+    let items: Vec<ListItem> = nodos
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(alto)
+        .map(|(i, nodo)| {
+            let icono = if nodo.es_hoja() {
+                "•"
+            } else if nodo.expandido {
+                "▾"
+            } else {
+                "▸"
+            };
+            let style = if i == app.arbol_seleccionado {
+                Style::default().bg(Color::Blue).fg(Color::White)
+            } else if nodo.profundidad == 0 {
+                Style::default().fg(Color::Yellow).bold()
+            } else {
+                Style::default()
+            };
+            ListItem::new(format!(
+                "{}{} {}",
+                "  ".repeat(nodo.profundidad),
+                icono,
+                nodo.etiqueta
+            ))
+            .style(style)
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::bordered()
+            .title(titulo(
+                "Competiciones",
+                app,
+                Contexto::Arbol,
+                &[
+                    (&[Accion::Aceptar], "Abrir/Ver"),
+                    (&[Accion::Izquierda], "Plegar"),
+                    (&[Accion::GuardarFiltro], "Guardar filtro"),
+                    (&[Accion::Siguiente], "Filtros"),
+                ],
+            ))
+            .border_style(Style::default().fg(Color::Cyan))
+            .borders(Borders::ALL),
+    );
+    f.render_widget(list, area);
+}
+
+fn campos_detalles(app: &App, p: &Partido) -> Vec<(&'static str, String)> {
+    let info = app.reglas_competicion.parsear(&p.competicion);
+    let estructura: Vec<String> = [
+        ("Categoría", &info.categoria),
+        ("Nivel", &info.nivel),
+        ("Grupo", &info.grupo),
+        ("Fase", &info.fase),
+        ("Territorio", &info.territorio),
+    ]
+    .iter()
+    .filter(|(_, valor)| !valor.is_empty())
+    .map(|(campo, valor)| format!("{}: {}", campo, valor))
+    .collect();
+
+    vec![
+        ("Partido", format!("{} vs {}", p.local, p.visitante)),
+        ("Competición", p.competicion.clone()),
+        (
+            "Estructura",
+            if estructura.is_empty() {
+                "-".to_string()
+            } else {
+                estructura.join(" · ")
+            },
+        ),
+        ("Fecha", p.data.clone()),
+        ("Hora", p.hora.clone()),
+        (
+            "Resultado",
+            if p.resultado.is_empty() {
+                "-".to_string()
+            } else {
+                p.resultado.clone()
+            },
+        ),
+        ("Pista", p.pista.clone()),
+        (
+            "Dirección",
+            pistas::buscar_pista(&app.pistas, &p.pista)
+                .map(|pista| pista.direccion.clone())
+                .filter(|d| !d.is_empty())
+                .unwrap_or_else(|| "- (sin registrar en pistas.json)".to_string()),
+        ),
+        (
+            "Trayecto",
+            match pistas::trayecto(&app.pistas, p) {
+                Some(t) => format!(
+                    "{:.1} km en línea recta, ~{} min",
+                    t.distancia_km, t.minutos
+                ),
+                None => "-".to_string(),
+            },
+        ),
+        (
+            "Salida",
+            pistas::trayecto(&app.pistas, p)
+                .and_then(|t| pistas::hora_salida(&app.pistas, p, &t))
+                .map(|h| {
+                    format!(
+                        "{} ({} min de margen)",
+                        h.format("%H:%M"),
+                        app.pistas.margen_minutos
+                    )
+                })
+                .unwrap_or_else(|| "-".to_string()),
+        ),
+        (
+            "Pronóstico",
+            if p.resultado.is_empty() {
+                let pr = app.ratings.pronostico(p);
+                format!(
+                    "Local {:.0}% · Empate {:.0}% · Visitante {:.0}%",
+                    pr.local * 100.0,
+                    pr.empate * 100.0,
+                    pr.visitante * 100.0
+                )
+            } else {
+                "-".to_string()
+            },
+        ),
+        (
+            "Historial",
+            match app.historia(p) {
+                Some(h) => {
+                    let mut texto =
+                        format!("En la agenda desde {}", historico::formatear(h.primera_vez));
+                    if h.aplazamientos() > 0 {
+                        let fechas: Vec<String> = h
+                            .fechas
+                            .iter()
+                            .map(|f| format!("{} {}", f.data, f.hora).trim().to_string())
+                            .collect();
+                        texto.push_str(&format!(
+                            " · {} cambio(s) de fecha: {}",
+                            h.aplazamientos(),
+                            fechas.join(" → ")
+                        ));
+                    }
+                    if let Some(publicado) = h.resultado_publicado {
+                        texto.push_str(&format!(
+                            " · resultado publicado {}",
+                            historico::formatear(publicado)
+                        ));
+                    }
+                    texto
+                }
+                None => "-".to_string(),
+            },
+        ),
+        (
+            "Origen",
+            match p.origen {
+                Origen::Fecapa => "FECAPA".to_string(),
+                Origen::Manual => "Manual (partidos_manuales.json)".to_string(),
+            },
+        ),
+        (
+            "Favorito",
+            if app.anotaciones.es_favorito(p) {
+                "★ Sí".to_string()
+            } else {
+                "No".to_string()
+            },
+        ),
+        (
+            "Notas",
+            app.anotaciones
+                .nota(p)
+                .cloned()
+                .unwrap_or_else(|| "-".to_string()),
+        ),
+    ]
+}
+
+pub fn num_campos_detalles(app: &App) -> usize {
+    app.partidos
+        .get(app.partido_seleccionado)
+        .map(|p| campos_detalles(app, p).len())
+        .unwrap_or(0)
+}
+
 pub fn render_detalles(f: &mut Frame, area: Rect, app: &App) {
     if let Some(p) = app.partidos.get(app.partido_seleccionado) {
-        let fields = vec![
-            ("Partido", format!("{} vs {}", p.local, p.visitante)),
-            ("Competición", p.competicion.clone()),
-            ("Fecha", p.data.clone()),
-            ("Hora", p.hora.clone()),
-            (
-                "Resultado",
-                if p.resultado.is_empty() {
-                    "-".to_string()
-                } else {
-                    p.resultado.clone()
-                },
-            ),
-            ("Pista", p.pista.clone()),
-        ];
+        let fields = campos_detalles(app, p);
 
         let text: Vec<Line> = fields
             .iter()
@@ -250,18 +532,123 @@ pub fn render_detalles(f: &mut Frame, area: Rect, app: &App) {
             })
             .collect();
 
+        let (area_campos, area_acta) = match app.acta(p) {
+            Some(acta) => {
+                let chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Length(fields.len() as u16 + 2),
+                        Constraint::Min(0),
+                    ])
+                    .split(area);
+                (chunks[0], Some((chunks[1], acta)))
+            }
+            None => (area, None),
+        };
+
         let paragraph = Paragraph::new(text)
             .block(
                 Block::bordered()
-                    .title(" Detalles - ↑↓ Navegar | A Añadir filtro | D Añadir competicion | Esc Volver ")
+                    .title(titulo(
+                        "Detalles",
+                        app,
+                        Contexto::Detalles,
+                        &[
+                            (&[Accion::Subir, Accion::Bajar], "Navegar"),
+                            (&[Accion::FiltrarLocal], "Añadir filtro"),
+                            (&[Accion::FiltrarCompeticion], "Añadir competición"),
+                            (&[Accion::Volver], "Volver"),
+                        ],
+                    ))
                     .border_style(Style::default().fg(Color::Cyan))
                     .borders(Borders::ALL),
             )
             .wrap(ratatui::widgets::Wrap { trim: false });
-        f.render_widget(paragraph, area);
+        f.render_widget(paragraph, area_campos);
+
+        if let Some((area_acta, acta)) = area_acta {
+            render_acta(f, area_acta, p, acta);
+        }
+    }
+}
+
+fn render_acta(f: &mut Frame, area: Rect, p: &Partido, acta: &Acta) {
+    let columnas = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    for (i, (lado, equipo, jugadores)) in [
+        (Lado::Local, &p.local, &acta.local),
+        (Lado::Visitante, &p.visitante, &acta.visitante),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let items: Vec<ListItem> = jugadores
+            .iter()
+            .map(|j| {
+                let mut linea = format!("{:>3} {}", j.dorsal, j.nombre);
+                let goles = acta.contar(lado, &j.nombre, TipoEvento::Gol);
+                if goles > 0 {
+                    linea.push_str(&format!("  ⚽×{}", goles));
+                }
+                for _ in 0..acta.contar(lado, &j.nombre, TipoEvento::Azul) {
+                    linea.push_str(" 🟦");
+                }
+                for _ in 0..acta.contar(lado, &j.nombre, TipoEvento::Roja) {
+                    linea.push_str(" 🟥");
+                }
+                ListItem::new(linea)
+            })
+            .collect();
+
+        let titulo = if i == 0 && !acta.arbitros.is_empty() {
+            format!(
+                " Acta: {} | Árbitros: {} ",
+                equipo,
+                acta.arbitros.join(", ")
+            )
+        } else {
+            format!(" Acta: {} ", equipo)
+        };
+        let list = List::new(items).block(
+            Block::bordered()
+                .title(titulo)
+                .border_style(Style::default().fg(Color::Magenta))
+                .borders(Borders::ALL),
+        );
+        f.render_widget(list, columnas[i]);
     }
 }
 
+/// La lista de partidos con la línea de comandos debajo.
+pub fn render_comandos(f: &mut Frame, area: Rect, app: &App) {
+    let partes = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(3)])
+        .split(area);
+    render_partidos_table(f, partes[0], app);
+    let linea = Paragraph::new(format!(":{}", app.paleta.texto))
+        .style(Style::default().fg(Color::Yellow))
+        .block(
+            Block::bordered()
+                .title(titulo(
+                    "COMANDO",
+                    app,
+                    Contexto::Comandos,
+                    &[
+                        (&[Accion::Completar], "Completar"),
+                        (&[Accion::Aceptar], "Ejecutar"),
+                        (&[Accion::Volver], "Cancelar"),
+                    ],
+                ))
+                .border_style(Style::default().fg(Color::Green))
+                .borders(Borders::ALL),
+        );
+    f.render_widget(linea, partes[1]);
+}
+
 pub fn render_buscar(f: &mut Frame, area: Rect, app: &App) {
     let search_prompt = Paragraph::new(format!("/{}", app.buscar_texto))
         .style(Style::default().fg(Color::Yellow))
@@ -276,11 +663,13 @@ pub fn render_buscar(f: &mut Frame, area: Rect, app: &App) {
 
 pub fn render_confirm(f: &mut Frame, area: Rect, app: &App) {
     let pregunta = match app.confirm_type {
-        Some(crate::models::ConfirmType::DeleteFilter) => "Eliminar este filtro?",
+        Some(crate::vistas::ConfirmType::DeleteFilter) => "Eliminar este filtro?",
+        Some(crate::vistas::ConfirmType::DeleteManual) => "Eliminar este partido?",
+        Some(crate::vistas::ConfirmType::DeletePlayer) => "Eliminar este jugador?",
         None => "",
     };
 
-    let opciones = vec!["Sí", "No"];
+    let opciones = ["Sí", "No"];
     let items: Vec<ListItem> = opciones
         .iter()
         .enumerate()
@@ -324,66 +713,974 @@ pub fn render_confirm(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(list, horizontal_area);
 }
 
-pub fn render_help(f: &mut Frame, area: Rect, app: &App) {
-    let (titulo, keys) = match app.vista_actual {
-        Vista::Partidos => (
-            "AYUDA - PARTIDOS",
-            vec![
-                ("↑ / ↓", "Navegar por partidos"),
-                ("AvPag / RePag", "Página siguiente/anterior"),
-                ("Enter", "Ver detalles del partido"),
-                ("F", "Ver lista de filtros"),
-                ("/", "Buscar texto"),
-                ("R", "Refrescar datos (scraper o GitHub)"),
-                ("?", "Ver esta ayuda"),
-                ("Q", "Salir de la aplicación"),
-            ],
-        ),
-        Vista::Filtros => (
-            "AYUDA - FILTROS",
-            vec![
-                ("↑ / ↓", "Navegar por filtros"),
-                ("Enter", "Aplicar filtro seleccionado"),
-                ("D", "Eliminar filtro (excepto 'Todos')"),
-                ("Esc", "Volver a partidos"),
-                ("?", "Ver esta ayuda"),
-            ],
-        ),
-        Vista::Detalles => (
-            "AYUDA - DETALLES",
-            vec![
-                ("↑ / ↓", "Navegar por campos"),
-                ("A", "Añadir equipo local como filtro"),
-                ("C", "Añadir competición como filtro"),
-                ("Esc", "Volver a partidos"),
-                ("?", "Ver esta ayuda"),
-            ],
-        ),
-        Vista::Buscar => (
-            "AYUDA - BUSCAR",
-            vec![
-                ("A-Z, 0-9", "Escribir texto de búsqueda"),
-                ("Backspace", "Borrar último carácter"),
-                ("Enter", "Confirmar búsqueda"),
-                ("Esc", "Cancelar búsqueda"),
-                ("?", "Ver esta ayuda"),
-            ],
-        ),
-        Vista::Confirm => (
-            "AYUDA - CONFIRMACIÓN",
-            vec![
-                ("↑ / ↓", "Seleccionar opción"),
-                ("Enter", "Confirmar acción"),
-                ("Esc", "Cancelar y volver"),
-                ("?", "Ver esta ayuda"),
-            ],
-        ),
-        _ => ("AYUDA", vec![("Esc", "Cerrar ayuda")]),
+pub fn render_formulario(f: &mut Frame, area: Rect, app: &App) {
+    let text: Vec<Line> = CAMPOS_FORMULARIO
+        .iter()
+        .zip(app.formulario.campos.iter())
+        .enumerate()
+        .map(|(i, (label, value))| {
+            if i == app.formulario.campo {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{}: ", label),
+                        Style::default()
+                            .bold()
+                            .fg(Color::Yellow)
+                            .bg(Color::DarkGray),
+                    ),
+                    Span::styled(
+                        format!("{}_", value),
+                        Style::default().fg(Color::White).bg(Color::DarkGray),
+                    ),
+                ])
+            } else {
+                Line::from(vec![
+                    Span::styled(format!("{}: ", label), Style::default().bold()),
+                    Span::raw(value.clone()),
+                ])
+            }
+        })
+        .collect();
+
+    let titulo = titulo(
+        if app.formulario.editando.is_some() {
+            "Editar partido manual"
+        } else {
+            "Nuevo partido manual"
+        },
+        app,
+        Contexto::Formulario,
+        &[
+            (&[Accion::Subir, Accion::Bajar], "Campo"),
+            (&[Accion::Aceptar], "Guardar"),
+            (&[Accion::Volver], "Cancelar"),
+        ],
+    );
+
+    let paragraph = Paragraph::new(text).block(
+        Block::bordered()
+            .title(titulo)
+            .border_style(Style::default().fg(Color::Magenta))
+            .borders(Borders::ALL),
+    );
+    f.render_widget(paragraph, area);
+}
+
+pub fn render_nota(f: &mut Frame, area: Rect, app: &App) {
+    let partido = app
+        .partidos
+        .get(app.partido_seleccionado)
+        .map(|p| format!(" NOTA - {} vs {} ", p.local, p.visitante))
+        .unwrap_or_else(|| " NOTA ".to_string());
+    let nota = Paragraph::new(format!("{}_", app.nota_texto))
+        .style(Style::default().fg(Color::Yellow))
+        .block(
+            Block::bordered()
+                .title(partido)
+                .border_style(Style::default().fg(Color::Green))
+                .borders(Borders::ALL),
+        )
+        .wrap(ratatui::widgets::Wrap { trim: false });
+    f.render_widget(nota, area);
+}
+
+pub fn render_asistencia(f: &mut Frame, area: Rect, app: &App) {
+    let plantilla = app.plantilla();
+    let proximos = app.proximos_partidos();
+    let equipo = app.filtro_equipo().map(|f| f.nombre.as_str()).unwrap_or("");
+
+    let columnas = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    let partidos: Vec<ListItem> = proximos
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let [si, no, quiza, pendiente] = app.asistencia.resumen(p, plantilla);
+            let style = if i == app.asistencia_partido {
+                Style::default().bg(Color::Blue).fg(Color::White)
+            } else {
+                Style::default()
+            };
+            ListItem::new(format!(
+                "{} {} {} - {}  ✔{} ✘{} ?{} ·{}",
+                p.data,
+                p.hora,
+                truncate(&p.local, 15),
+                truncate(&p.visitante, 15),
+                si,
+                no,
+                quiza,
+                pendiente
+            ))
+            .style(style)
+        })
+        .collect();
+
+    let lista_partidos = List::new(partidos).block(
+        Block::bordered()
+            .title(format!(" Próximos partidos - {} ", equipo))
+            .border_style(Style::default().fg(Color::Cyan))
+            .borders(Borders::ALL),
+    );
+    f.render_widget(lista_partidos, columnas[0]);
+
+    let partido = proximos.get(app.asistencia_partido);
+    let mut jugadores: Vec<ListItem> = plantilla
+        .iter()
+        .enumerate()
+        .map(|(i, jugador)| {
+            let (icono, color) = match partido.and_then(|p| app.asistencia.estado(p, jugador)) {
+                Some(Disponibilidad::Si) => ("✅", Color::Green),
+                Some(Disponibilidad::No) => ("❌", Color::Red),
+                Some(Disponibilidad::Quiza) => ("❓", Color::Yellow),
+                None => ("⏳", Color::Gray),
+            };
+            let style = if i == app.asistencia_jugador {
+                Style::default().bg(Color::Blue).fg(Color::White)
+            } else {
+                Style::default().fg(color)
+            };
+            ListItem::new(format!("{} {}", icono, jugador)).style(style)
+        })
+        .collect();
+
+    if let Some(entrada) = &app.asistencia_entrada {
+        jugadores.push(
+            ListItem::new(format!("+ {}_", entrada)).style(Style::default().fg(Color::Yellow)),
+        );
+    } else if plantilla.is_empty() {
+        let pulsa = app
+            .teclado
+            .tecla(Contexto::Asistencia, Accion::NuevoJugador)
+            .map(|tecla| format!(": pulsa {} para añadir jugadores", tecla))
+            .unwrap_or_default();
+        jugadores.push(ListItem::new(format!("Plantilla vacía{}", pulsa)));
+    }
+
+    let lista_jugadores = List::new(jugadores).block(
+        Block::bordered()
+            .title(titulo(
+                "Disponibilidad",
+                app,
+                Contexto::Asistencia,
+                &[
+                    (&[Accion::Disponibilidad], "Cambiar"),
+                    (&[Accion::NuevoJugador], "Añadir"),
+                    (&[Accion::Eliminar], "Quitar"),
+                    (&[Accion::Exportar], "Exportar"),
+                ],
+            ))
+            .border_style(Style::default().fg(Color::Cyan))
+            .borders(Borders::ALL),
+    );
+    f.render_widget(lista_jugadores, columnas[1]);
+}
+
+pub fn render_cara_a_cara(f: &mut Frame, area: Rect, app: &App) {
+    let Some(p) = app.partidos.get(app.partido_seleccionado) else {
+        return;
+    };
+    let h2h = enfrentamientos::cara_a_cara(app.historial_partidos(), &app.todos_partidos, p);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(5), Constraint::Min(0)])
+        .split(area);
+
+    let vuelta = match &h2h.vuelta {
+        Some(v) if !v.resultado.is_empty() => {
+            format!("{} {} {} ({})", v.local, v.resultado, v.visitante, v.data)
+        }
+        Some(v) => format!("{} vs {} - pendiente ({})", v.local, v.visitante, v.data),
+        None => "-".to_string(),
+    };
+    let resumen = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled(format!("{}: ", h2h.equipo), Style::default().bold()),
+            Span::styled(
+                format!("{}G ", h2h.ganados),
+                Style::default().fg(Color::Green),
+            ),
+            Span::styled(
+                format!("{}E ", h2h.empatados),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::styled(
+                format!("{}P", h2h.perdidos),
+                Style::default().fg(Color::Red),
+            ),
+            Span::raw(format!(
+                "  |  Goles {} - {}",
+                h2h.goles_favor, h2h.goles_contra
+            )),
+        ]),
+        Line::from(vec![
+            Span::styled("Partido de vuelta: ", Style::default().bold()),
+            Span::raw(vuelta),
+        ]),
+    ])
+    .block(
+        Block::bordered()
+            .title(format!(" Cara a cara: {} vs {} ", h2h.equipo, h2h.rival))
+            .border_style(Style::default().fg(Color::Cyan))
+            .borders(Borders::ALL),
+    );
+    f.render_widget(resumen, chunks[0]);
+
+    let rows: Vec<Row> = h2h
+        .partidos
+        .iter()
+        .map(|o| {
+            let style = if o == p {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default()
+            };
+            Row::new(vec![
+                Cell::from(o.data.clone()),
+                Cell::from(truncate(&o.competicion, 25)),
+                Cell::from(truncate(&o.local, 20)),
+                Cell::from(truncate(&o.visitante, 20)),
+                Cell::from(if o.resultado.is_empty() {
+                    "-".to_string()
+                } else {
+                    o.resultado.clone()
+                }),
+            ])
+            .style(style)
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(10),
+            Constraint::Min(20),
+            Constraint::Min(15),
+            Constraint::Min(15),
+            Constraint::Length(8),
+        ],
+    )
+    .header(
+        Row::new(vec!["FECHA", "COMPETICIÓN", "LOCAL", "VISITANTE", "RES"])
+            .style(Style::default().fg(Color::Yellow).bold()),
+    )
+    .block(
+        Block::bordered()
+            .title(titulo(
+                &format!("Enfrentamientos ({})", h2h.partidos.len()),
+                app,
+                Contexto::CaraACara,
+                &[(&[Accion::Volver], "Volver")],
+            ))
+            .border_style(Style::default().fg(Color::Cyan))
+            .borders(Borders::ALL),
+    );
+    f.render_widget(table, chunks[1]);
+}
+
+pub fn render_ratings(f: &mut Frame, area: Rect, app: &App) {
+    let competiciones = app.ratings.competiciones();
+    let Some(competicion) = competiciones.get(app.ratings_competicion) else {
+        return;
+    };
+
+    let rows: Vec<Row> = app
+        .ratings
+        .tabla(competicion)
+        .iter()
+        .enumerate()
+        .map(|(i, r)| {
+            Row::new(vec![
+                Cell::from(format!("{}", i + 1)),
+                Cell::from(truncate(&r.equipo, 35)),
+                Cell::from(format!("{:.0}", r.rating)),
+                Cell::from(format!("{}", r.jugados)),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(4),
+            Constraint::Min(20),
+            Constraint::Length(8),
+            Constraint::Length(4),
+        ],
+    )
+    .header(
+        Row::new(vec!["#", "EQUIPO", "RATING", "PJ"])
+            .style(Style::default().fg(Color::Yellow).bold()),
+    )
+    .block(
+        Block::bordered()
+            .title(titulo(
+                &format!(
+                    "Ratings Elo - {} ({}/{})",
+                    competicion,
+                    app.ratings_competicion + 1,
+                    competiciones.len()
+                ),
+                app,
+                Contexto::Ratings,
+                &[
+                    (&[Accion::Izquierda, Accion::Derecha], "Competición"),
+                    (&[Accion::Volver], "Volver"),
+                ],
+            ))
+            .border_style(Style::default().fg(Color::Cyan))
+            .borders(Borders::ALL),
+    );
+    f.render_widget(table, area);
+}
+
+pub fn render_errores(f: &mut Frame, area: Rect, app: &App) {
+    let mut lines = Vec::new();
+    for (i, error) in app.errores.iter().enumerate() {
+        let style = if i == app.error_seleccionado {
+            Style::default().bg(Color::Blue).fg(Color::White)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::styled(
+            format!("{}", error.ruta().display()),
+            style.fg(Color::Yellow).bold(),
+        ));
+        let detalles = match error {
+            ErrorDatos::Formato {
+                linea,
+                columna,
+                error,
+                ..
+            } => vec![format!("Línea {}, columna {}: {}", linea, columna, error)],
+            ErrorDatos::Lectura { error, .. } | ErrorDatos::Escritura { error, .. } => {
+                vec![error.clone()]
+            }
+            ErrorDatos::Bloqueado { .. } => {
+                vec!["No se pudo cargar; los cambios no se guardan hasta apartarlo".to_string()]
+            }
+            ErrorDatos::Contenido { problemas, .. } => problemas.clone(),
+        };
+        for detalle in detalles {
+            lines.push(Line::styled(format!("  {}", detalle), style));
+        }
+        lines.push(Line::from(""));
+    }
+    if let Some(tecla) = app.teclado.tecla(Contexto::Errores, Accion::ApartarArchivo) {
+        lines.push(Line::from(format!(
+            "Corrige el archivo y reinicia, o pulsa {} para apartarlo: se renombra a \
+             <archivo>.corrupto-<fecha> y se vuelve a guardar con los datos actuales.",
+            tecla
+        )));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::bordered()
+                .title(titulo(
+                    "Errores en los archivos",
+                    app,
+                    Contexto::Errores,
+                    &[
+                        (&[Accion::Subir, Accion::Bajar], "Seleccionar"),
+                        (&[Accion::ApartarArchivo], "Apartar"),
+                        (&[Accion::Volver], "Volver"),
+                    ],
+                ))
+                .border_style(Style::default().fg(Color::Red))
+                .borders(Borders::ALL),
+        )
+        .wrap(ratatui::widgets::Wrap { trim: false });
+    f.render_widget(paragraph, area);
+}
+
+pub fn render_historial(f: &mut Frame, area: Rect, app: &App) {
+    let rows: Vec<Row> = app
+        .historial
+        .iter()
+        .map(|h| {
+            let b = &h.balance;
+            let style = if h.temporada == app.temporada {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default()
+            };
+            Row::new(vec![
+                Cell::from(h.temporada.clone()),
+                Cell::from(truncate(&h.competicion, 30)),
+                Cell::from(truncate(&h.equipo, 25)),
+                Cell::from(match h.posicion {
+                    Some((pos, total)) => format!("{}/{}", pos, total),
+                    None => "-".to_string(),
+                }),
+                Cell::from(format!("{}", b.jugados)),
+                Cell::from(format!("{}", b.ganados)),
+                Cell::from(format!("{}", b.empatados)),
+                Cell::from(format!("{}", b.perdidos)),
+                Cell::from(format!("{}", b.goles_favor)),
+                Cell::from(format!("{}", b.goles_contra)),
+                Cell::from(format!("{}", b.puntos())),
+            ])
+            .style(style)
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(8),
+            Constraint::Min(15),
+            Constraint::Min(15),
+            Constraint::Length(6),
+            Constraint::Length(4),
+            Constraint::Length(4),
+            Constraint::Length(4),
+            Constraint::Length(4),
+            Constraint::Length(4),
+            Constraint::Length(4),
+            Constraint::Length(4),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "TEMP.",
+            "COMPETICIÓN",
+            "EQUIPO",
+            "POS",
+            "PJ",
+            "G",
+            "E",
+            "P",
+            "GF",
+            "GC",
+            "PTS",
+        ])
+        .style(Style::default().fg(Color::Yellow).bold()),
+    )
+    .block(
+        Block::bordered()
+            .title(titulo(
+                "Historial por temporadas",
+                app,
+                Contexto::Historial,
+                &[(&[Accion::Volver], "Volver")],
+            ))
+            .border_style(Style::default().fg(Color::Cyan))
+            .borders(Borders::ALL),
+    );
+    f.render_widget(table, area);
+}
+
+pub fn render_jugadores(f: &mut Frame, area: Rect, app: &App) {
+    let table_height = (area.height as usize).saturating_sub(3);
+    let offset = app.jugadores_seleccionado.saturating_sub(table_height / 2);
+
+    let rows: Vec<Row> = app
+        .jugadores
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(table_height)
+        .map(|(i, j)| {
+            let style = if i == app.jugadores_seleccionado {
+                Style::default().bg(Color::Blue).fg(Color::White)
+            } else if j.rojas > 0 {
+                Style::default().fg(Color::Red)
+            } else if j.en_riesgo() {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default()
+            };
+            let ciclo = format!(
+                "{}{}/{}",
+                if j.en_riesgo() { "⚠ " } else { "" },
+                j.azules % AZULES_POR_SANCION,
+                AZULES_POR_SANCION
+            );
+            Row::new(vec![
+                Cell::from(format!("{}", i + 1)),
+                Cell::from(truncate(&j.nombre, 25)),
+                Cell::from(truncate(&j.equipo, 25)),
+                Cell::from(format!("{}", j.partidos)),
+                Cell::from(format!("{}", j.goles)),
+                Cell::from(format!("{:.2}", j.goles_por_partido())),
+                Cell::from(format!("{}", j.azules)),
+                Cell::from(format!("{}", j.rojas)),
+                Cell::from(ciclo),
+            ])
+            .style(style)
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(4),
+            Constraint::Min(15),
+            Constraint::Min(15),
+            Constraint::Length(4),
+            Constraint::Length(4),
+            Constraint::Length(6),
+            Constraint::Length(4),
+            Constraint::Length(4),
+            Constraint::Length(10),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "#", "JUGADOR", "EQUIPO", "PJ", "GOL", "G/PJ", "AZ", "RO", "CICLO AZ",
+        ])
+        .style(Style::default().fg(Color::Yellow).bold()),
+    )
+    .block(
+        Block::bordered()
+            .title(titulo(
+                &format!(
+                    "Jugadores - {} - orden: {}",
+                    app.ambito_jugadores(),
+                    app.jugadores_orden.etiqueta()
+                ),
+                app,
+                Contexto::Jugadores,
+                &[
+                    (&[Accion::Izquierda, Accion::Derecha], "Competición"),
+                    (&[Accion::Siguiente], "Equipo"),
+                    (&[Accion::Ordenar], "Orden"),
+                    (&[Accion::Exportar], "Exportar"),
+                    (&[Accion::Volver], "Volver"),
+                ],
+            ))
+            .border_style(Style::default().fg(Color::Cyan))
+            .borders(Borders::ALL),
+    );
+    f.render_widget(table, area);
+}
+
+fn maximo(series: &[&[(f64, f64)]], eje_y: bool) -> f64 {
+    series
+        .iter()
+        .flat_map(|s| s.iter())
+        .map(|(x, y)| if eje_y { *y } else { *x })
+        .fold(1.0, f64::max)
+}
+
+pub fn render_estadisticas(f: &mut Frame, area: Rect, app: &App) {
+    let Some(stats) = &app.estadisticas else {
+        return;
+    };
+
+    let filas = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(area);
+    let arriba = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(filas[0]);
+    let abajo = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(filas[1]);
+
+    let max_x = maximo(&[&stats.goles_favor], false);
+    let max_y = maximo(&[&stats.goles_favor, &stats.goles_contra], true);
+    let goles = Chart::new(vec![
+        Dataset::default()
+            .name("A favor")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Green))
+            .data(&stats.goles_favor),
+        Dataset::default()
+            .name("En contra")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Red))
+            .data(&stats.goles_contra),
+    ])
+    .block(
+        Block::bordered()
+            .title(format!(" Goles por partido - {} ", stats.equipo))
+            .border_style(Style::default().fg(Color::Cyan))
+            .borders(Borders::ALL),
+    )
+    .x_axis(
+        Axis::default()
+            .title("Partido")
+            .bounds([1.0, max_x])
+            .labels(vec!["1".to_string(), format!("{:.0}", max_x)]),
+    )
+    .y_axis(
+        Axis::default()
+            .bounds([0.0, max_y])
+            .labels(vec!["0".to_string(), format!("{:.0}", max_y)]),
+    );
+    f.render_widget(goles, arriba[0]);
+
+    let colores = [Color::Yellow, Color::Cyan, Color::Magenta, Color::Blue];
+    let series: Vec<&[(f64, f64)]> = stats.puntos.iter().map(|l| l.puntos.as_slice()).collect();
+    let max_dias = maximo(&series, false);
+    let max_puntos = maximo(&series, true);
+    let datasets: Vec<Dataset> = stats
+        .puntos
+        .iter()
+        .zip(colores.iter().cycle())
+        .map(|(linea, color)| {
+            Dataset::default()
+                .name(truncate(&linea.equipo, 20))
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(*color))
+                .data(&linea.puntos)
+        })
+        .collect();
+    let puntos = Chart::new(datasets)
+        .block(
+            Block::bordered()
+                .title(format!(" Puntos acumulados - {} ", stats.competicion))
+                .border_style(Style::default().fg(Color::Cyan))
+                .borders(Borders::ALL),
+        )
+        .x_axis(
+            Axis::default()
+                .title("Días")
+                .bounds([0.0, max_dias])
+                .labels(vec!["0".to_string(), format!("{:.0}", max_dias)]),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds([0.0, max_puntos])
+                .labels(vec!["0".to_string(), format!("{:.0}", max_puntos)]),
+        );
+    f.render_widget(puntos, arriba[1]);
+
+    let total = stats.total();
+    let resultados = [
+        ("Gan", total.ganados as u64),
+        ("Emp", total.empatados as u64),
+        ("Per", total.perdidos as u64),
+    ];
+    let barras = BarChart::default()
+        .block(
+            Block::bordered()
+                .title(" Resultados ")
+                .border_style(Style::default().fg(Color::Cyan))
+                .borders(Borders::ALL),
+        )
+        .data(&resultados)
+        .bar_width(5)
+        .bar_gap(2)
+        .bar_style(Style::default().fg(Color::Green))
+        .value_style(Style::default().fg(Color::Black).bg(Color::Green));
+    f.render_widget(barras, abajo[0]);
+
+    let fila = |nombre: &str, b: &fecapa::estadisticas::Balance| {
+        Row::new(vec![
+            Cell::from(nombre.to_string()),
+            Cell::from(b.jugados.to_string()),
+            Cell::from(b.ganados.to_string()),
+            Cell::from(b.empatados.to_string()),
+            Cell::from(b.perdidos.to_string()),
+            Cell::from(b.goles_favor.to_string()),
+            Cell::from(b.goles_contra.to_string()),
+            Cell::from(b.puntos().to_string()),
+        ])
     };
+    let splits = Table::new(
+        vec![
+            fila("Casa", &stats.casa),
+            fila("Fuera", &stats.fuera),
+            fila("Total", &total).style(Style::default().bold()),
+        ],
+        [
+            Constraint::Length(7),
+            Constraint::Length(4),
+            Constraint::Length(4),
+            Constraint::Length(4),
+            Constraint::Length(4),
+            Constraint::Length(4),
+            Constraint::Length(4),
+            Constraint::Length(5),
+        ],
+    )
+    .header(
+        Row::new(vec!["", "PJ", "G", "E", "P", "GF", "GC", "PTS"])
+            .style(Style::default().fg(Color::Yellow).bold()),
+    )
+    .block(
+        Block::bordered()
+            .title(titulo(
+                "Casa / Fuera",
+                app,
+                Contexto::Estadisticas,
+                &[(&[Accion::Volver], "Volver")],
+            ))
+            .border_style(Style::default().fg(Color::Cyan))
+            .borders(Borders::ALL),
+    );
+    f.render_widget(splits, abajo[1]);
+}
+
+pub fn render_clasificacion(f: &mut Frame, area: Rect, app: &App) {
+    let Some(competicion) = app.competicion_clasificacion() else {
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(area);
+
+    let tabs = Tabs::new(vec![" Clasificación ", " Proyección "])
+        .select(app.clasificacion_pestana)
+        .highlight_style(Style::default().fg(Color::Yellow).bold())
+        .block(
+            Block::bordered()
+                .title(titulo(
+                    &competicion,
+                    app,
+                    Contexto::Clasificacion,
+                    &[
+                        (&[Accion::Izquierda, Accion::Derecha], "Competición"),
+                        (&[Accion::Siguiente], "Pestaña"),
+                        (&[Accion::Volver], "Volver"),
+                    ],
+                ))
+                .border_style(Style::default().fg(Color::Cyan))
+                .borders(Borders::ALL),
+        );
+    f.render_widget(tabs, chunks[0]);
 
-    let _title_block = Paragraph::new(titulo)
-        .style(Style::default().fg(Color::Yellow).bold())
-        .alignment(ratatui::layout::Alignment::Center);
+    if app.clasificacion_pestana == 0 {
+        let filas = clasificacion::clasificacion(&app.todos_partidos, &competicion);
+        render_tabla_clasificacion(f, chunks[1], &filas, " Clasificación ");
+        return;
+    }
+
+    let rows: Vec<Row> = app
+        .proyeccion
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            let color = if e.peor_posicion == 1 {
+                Color::Green
+            } else if e.mejor_posicion > 1 {
+                Color::DarkGray
+            } else {
+                Color::White
+            };
+            Row::new(vec![
+                Cell::from(format!("{}", i + 1)),
+                Cell::from(truncate(&e.equipo, 25)),
+                Cell::from(e.puntos.to_string()),
+                Cell::from(e.pendientes.to_string()),
+                Cell::from(e.puntos_maximos.to_string()),
+                Cell::from(format!("{}º-{}º", e.mejor_posicion, e.peor_posicion)),
+                Cell::from(
+                    e.puntos_para_titulo
+                        .map(|p| p.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                ),
+                Cell::from(format!("{:.0}%", e.prob_primero * 100.0)),
+                Cell::from(format!("{:.0}%", e.prob_ultimo * 100.0)),
+                Cell::from(format!("{:.1}", e.posicion_media)),
+                Cell::from(e.estado()),
+            ])
+            .style(Style::default().fg(color))
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(3),
+            Constraint::Min(15),
+            Constraint::Length(4),
+            Constraint::Length(4),
+            Constraint::Length(4),
+            Constraint::Length(8),
+            Constraint::Length(5),
+            Constraint::Length(5),
+            Constraint::Length(5),
+            Constraint::Length(5),
+            Constraint::Min(18),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "#", "EQUIPO", "PTS", "PEN", "MAX", "POS", "NEC", "1º", "ÚLT", "MEDIA", "ESTADO",
+        ])
+        .style(Style::default().fg(Color::Yellow).bold()),
+    )
+    .block(
+        Block::bordered()
+            .title(format!(
+                " Proyección ({} simulaciones) - NEC: puntos para asegurar el 1º ",
+                clasificacion::SIMULACIONES
+            ))
+            .border_style(Style::default().fg(Color::Cyan))
+            .borders(Borders::ALL),
+    );
+    f.render_widget(table, chunks[1]);
+}
+
+pub fn render_jornadas(f: &mut Frame, area: Rect, app: &App) {
+    let Some(jornada) = app.jornadas.get(app.jornada_actual) else {
+        return;
+    };
+    let competicion = jornada
+        .partidos
+        .first()
+        .map(|p| p.competicion.clone())
+        .unwrap_or_default();
+
+    let alto_resultados = (jornada.partidos.len() as u16 + 3).min(area.height / 2);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(alto_resultados), Constraint::Min(0)])
+        .split(area);
+
+    let rows: Vec<Row> = jornada
+        .partidos
+        .iter()
+        .map(|p| {
+            let style = if p.resultado.is_empty() {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default()
+            };
+            Row::new(vec![
+                Cell::from(p.data.clone()),
+                Cell::from(p.hora.clone()),
+                Cell::from(truncate(&p.local, 25)),
+                Cell::from(if p.resultado.is_empty() {
+                    "-".to_string()
+                } else {
+                    p.resultado.clone()
+                }),
+                Cell::from(truncate(&p.visitante, 25)),
+            ])
+            .style(style)
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(10),
+            Constraint::Length(6),
+            Constraint::Min(15),
+            Constraint::Length(8),
+            Constraint::Min(15),
+        ],
+    )
+    .header(
+        Row::new(vec!["FECHA", "HORA", "LOCAL", "RES", "VISITANTE"])
+            .style(Style::default().fg(Color::Yellow).bold()),
+    )
+    .block(
+        Block::bordered()
+            .title(titulo(
+                &format!(
+                    "{} - Jornada {}/{} ({} - {})",
+                    competicion,
+                    jornada.numero,
+                    app.jornadas.len(),
+                    jornada.inicio.format("%d/%m"),
+                    jornada.fin.format("%d/%m/%Y")
+                ),
+                app,
+                Contexto::Jornadas,
+                &[
+                    (&[Accion::Izquierda, Accion::Derecha], "Jornada"),
+                    (&[Accion::Volver], "Volver"),
+                ],
+            ))
+            .border_style(Style::default().fg(Color::Cyan))
+            .borders(Borders::ALL),
+    );
+    f.render_widget(table, chunks[0]);
+
+    let filas = clasificacion::clasificacion_con(&app.todos_partidos, &competicion, |p| {
+        jornadas::hasta_la_jornada(&app.jornadas, app.jornada_actual, p)
+    });
+    render_tabla_clasificacion(
+        f,
+        chunks[1],
+        &filas,
+        &format!(" Clasificación tras la jornada {} ", jornada.numero),
+    );
+}
+
+pub fn render_tabla_clasificacion(
+    f: &mut Frame,
+    area: Rect,
+    filas: &[clasificacion::FilaClasificacion],
+    titulo: &str,
+) {
+    let rows: Vec<Row> = filas
+        .iter()
+        .enumerate()
+        .map(|(i, fila)| {
+            let b = &fila.balance;
+            Row::new(vec![
+                Cell::from(format!("{}", i + 1)),
+                Cell::from(truncate(&fila.equipo, 30)),
+                Cell::from(b.jugados.to_string()),
+                Cell::from(b.ganados.to_string()),
+                Cell::from(b.empatados.to_string()),
+                Cell::from(b.perdidos.to_string()),
+                Cell::from(b.goles_favor.to_string()),
+                Cell::from(b.goles_contra.to_string()),
+                Cell::from(format!("{:+}", fila.diferencia())),
+                Cell::from(b.puntos().to_string()).style(Style::default().bold()),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(3),
+            Constraint::Min(20),
+            Constraint::Length(4),
+            Constraint::Length(4),
+            Constraint::Length(4),
+            Constraint::Length(4),
+            Constraint::Length(4),
+            Constraint::Length(4),
+            Constraint::Length(5),
+            Constraint::Length(5),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "#", "EQUIPO", "PJ", "G", "E", "P", "GF", "GC", "DIF", "PTS",
+        ])
+        .style(Style::default().fg(Color::Yellow).bold()),
+    )
+    .block(
+        Block::bordered()
+            .title(titulo.to_string())
+            .border_style(Style::default().fg(Color::Cyan))
+            .borders(Borders::ALL),
+    );
+    f.render_widget(table, area);
+}
+
+pub fn render_help(f: &mut Frame, area: Rect, app: &App) {
+    // La ayuda es de la vista desde la que se abrió.
+    let vista = match app.vista_actual {
+        Vista::Help => app.vista_anterior,
+        otra => otra,
+    };
+    let contexto = Contexto::de_vista(vista, app);
+    let titulo = format!("AYUDA - {}", contexto.titulo());
+    let keys = app.teclado.ayuda(contexto);
 
     let keys_block: Vec<ListItem> = keys
         .iter()
@@ -422,26 +1719,77 @@ pub fn render_help(f: &mut Frame, area: Rect, app: &App) {
 }
 
 pub fn render_status(f: &mut Frame, area: Rect, app: &App) {
-    use crate::commands::is_android;
-
     let status_text = if app.scraping {
-        if is_android() {
-            "⏳ Descargando partidos de GitHub...".to_string()
-        } else {
-            "⏳ Extrayendo partidos de la web...".to_string()
-        }
+        let mut fuentes: Vec<_> = app.ajustes.fuentes.iter().collect();
+        fuentes.sort_by_key(|f| f.prioridad);
+        let fuentes: Vec<String> = fuentes.iter().map(|f| f.describir()).collect();
+        format!("⏳ Actualizando partidos ({})...", fuentes.join(" → "))
     } else {
         format!(
-            "{} | Filtro: {} | ↑↓/Av/Re Pag Navegar | Enter Ver | F Filtros | / Buscar | ? Ayuda | R Refrescar | Q Salir",
+            "{} | Filtro: {} | {}",
             app.mensaje,
             app.filtros
                 .get(app.filtro_seleccionado)
                 .map(|f| f.nombre.as_str())
-                .unwrap_or("Todos")
+                .unwrap_or("Todos"),
+            app.teclado.pistas(
+                Contexto::Partidos,
+                &[
+                    (&[Accion::Subir, Accion::Bajar], "Navegar"),
+                    (&[Accion::Detalles], "Ver"),
+                    (&[Accion::Filtros], "Filtros"),
+                    (&[Accion::Buscar], "Buscar"),
+                    (&[Accion::NuevoPartido], "Nuevo"),
+                    (&[Accion::Asistencia], "Disponib."),
+                    (&[Accion::Comandos], "Comandos"),
+                    (&[Accion::Ayuda], "Ayuda"),
+                    (&[Accion::Refrescar], "Refrescar"),
+                    (&[Accion::Salir], "Salir"),
+                ],
+            )
         )
     };
+    // Repeticiones a medio teclear (el 5 de `5j`).
+    let status_text = match app.cuenta {
+        Some(cuenta) => format!("{}… | {}", cuenta, status_text),
+        None => status_text,
+    };
+    // Pista entre paréntesis, o nada si la acción no tiene tecla.
+    let pista = |accion, texto| match app.teclado.tecla(Contexto::Partidos, accion) {
+        Some(tecla) => format!(" ({} {})", tecla, texto),
+        None => String::new(),
+    };
+    let (status_text, fondo, letra) = if !app.errores.is_empty() {
+        (
+            format!(
+                "⚠ {} archivo(s) con errores{} | {}",
+                app.errores.len(),
+                pista(Accion::Errores, "ver"),
+                status_text
+            ),
+            Color::Red,
+            Color::White,
+        )
+    } else if app.datos_caducados() && !app.scraping {
+        let aviso = match app.antiguedad() {
+            Some(antiguedad) => format!("Datos de {}", formatear_antiguedad(antiguedad)),
+            None => "Sin fecha de actualización".to_string(),
+        };
+        (
+            format!(
+                "⏰ {}{} | {}",
+                aviso,
+                pista(Accion::Refrescar, "refrescar"),
+                status_text
+            ),
+            Color::Yellow,
+            Color::Black,
+        )
+    } else {
+        (status_text, Color::Blue, Color::White)
+    };
     let status = Paragraph::new(status_text)
-        .style(Style::default().fg(Color::White).bg(Color::Blue))
+        .style(Style::default().fg(letra).bg(fondo))
         .alignment(ratatui::layout::Alignment::Left);
     f.render_widget(status, area);
 }
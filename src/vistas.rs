@@ -0,0 +1,98 @@
+use fecapa::models::{Origen, Partido};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Vista {
+    #[default]
+    Partidos,
+    Filtros,
+    Detalles,
+    Buscar,
+    Confirm,
+    Help,
+    Formulario,
+    Nota,
+    Asistencia,
+    CaraACara,
+    Ratings,
+    Estadisticas,
+    Clasificacion,
+    Jornadas,
+    Jugadores,
+    Historial,
+    Errores,
+    Comandos,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::enum_variant_names)]
+pub enum ConfirmType {
+    DeleteFilter,
+    DeleteManual,
+    DeletePlayer,
+}
+
+pub const CAMPOS_FORMULARIO: [&str; 7] = [
+    "Competición",
+    "Fecha",
+    "Hora",
+    "Local",
+    "Visitante",
+    "Resultado",
+    "Pista",
+];
+
+/// Línea de comandos que se abre con `:`.
+#[derive(Debug, Clone, Default)]
+pub struct Paleta {
+    pub texto: String,
+    /// Opciones de la última vez que se completó; Tab pasa a la siguiente.
+    pub candidatos: Vec<String>,
+    pub candidato: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FormularioPartido {
+    pub campos: [String; 7],
+    pub campo: usize,
+    pub editando: Option<usize>,
+}
+
+impl FormularioPartido {
+    pub fn nuevo() -> Self {
+        let mut formulario = Self::default();
+        formulario.campos[0] = "AMISTÓS".to_string();
+        formulario
+    }
+
+    pub fn desde_partido(p: &Partido, indice: usize) -> Self {
+        Self {
+            campos: [
+                p.competicion.clone(),
+                p.data.clone(),
+                p.hora.clone(),
+                p.local.clone(),
+                p.visitante.clone(),
+                p.resultado.clone(),
+                p.pista.clone(),
+            ],
+            campo: 0,
+            editando: Some(indice),
+        }
+    }
+
+    pub fn a_partido(&self) -> Partido {
+        let [competicion, data, hora, local, visitante, resultado, pista] =
+            self.campos.clone().map(|c| c.trim().to_string());
+        Partido {
+            competicion,
+            data,
+            hora,
+            local,
+            visitante,
+            resultado,
+            pista,
+            origen: Origen::Manual,
+            ..Default::default()
+        }
+    }
+}